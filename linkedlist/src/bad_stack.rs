@@ -1,48 +1,213 @@
 use std::mem;
 
-pub struct List {
-    head: Link,
+use crate::good_stack;
+
+pub struct List<T> {
+    head: Link<T>,
+    //push和pop维护的元素个数
+    len: usize,
 }
 
-enum Link {
+//和good_stack不同，这里用自定义的enum而不是Option来表示link
+enum Link<T> {
     Empty,
     //如果不放在堆上，则大小是动态的，编译报错
-    More(Box<Node>),
+    More(Box<Node<T>>),
 }
 
-struct Node {
-    elem: String,
-    next: Link,
+struct Node<T> {
+    elem: T,
+    //测试时在next前后各放一个探针，用来记录drop的嵌套深度；正式构建里没有这两个字段
+    #[cfg(test)]
+    _enter: depth_probe::Enter,
+    next: Link<T>,
+    #[cfg(test)]
+    _exit: depth_probe::Exit,
 }
 
-impl List {
+//字段按声明顺序drop：Enter在next之前把深度加一，Exit在next之后减一。
+//如果drop是递归的，next里的节点会在Exit之前drop，深度就会一直往上涨
+#[cfg(test)]
+mod depth_probe {
+    use std::cell::Cell;
+
+    thread_local! {
+        static DEPTH: Cell<usize> = const { Cell::new(0) };
+        static MAX_DEPTH: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub struct Enter;
+    pub struct Exit;
+
+    impl Drop for Enter {
+        fn drop(&mut self) {
+            let depth = DEPTH.get() + 1;
+            DEPTH.set(depth);
+            MAX_DEPTH.set(MAX_DEPTH.get().max(depth));
+        }
+    }
+
+    impl Drop for Exit {
+        fn drop(&mut self) {
+            DEPTH.set(DEPTH.get() - 1);
+        }
+    }
+
+    //清零并返回上一次清零之后记录到的最大深度
+    pub fn take_max_depth() -> usize {
+        MAX_DEPTH.replace(0)
+    }
+}
+
+impl<T> List<T> {
     pub fn new() -> Self {
-        List { head: Link::Empty }
+        List { head: Link::Empty, len: 0 }
     }
 
-    pub fn push(&mut self, elem: String) {
+    pub fn push(&mut self, elem: T) {
         let new_node = Box::new(Node {
-            elem: elem,
+            elem,
+            #[cfg(test)]
+            _enter: depth_probe::Enter,
             //replace方法把head的所有权置换出来赋值给next
             next: mem::replace(&mut self.head, Link::Empty),
+            #[cfg(test)]
+            _exit: depth_probe::Exit,
         });
 
         self.head = Link::More(new_node);
+        self.len += 1;
     }
 
-    pub fn pop(&mut self) -> Option<String> {
+    pub fn pop(&mut self) -> Option<T> {
         //这里也需要拿到所有权
         match mem::replace(&mut self.head, Link::Empty) {
             Link::Empty => None,
             Link::More(node) => {
                 self.head = node.next;
+                self.len -= 1;
                 Some(node.elem)
             }
         }
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    //原地把next指针反过来，只搬动Box，不拷贝元素；同样用mem::replace在Link之间倒腾所有权
+    pub fn reverse(&mut self) {
+        let mut prev = Link::Empty;
+        let mut cur = mem::replace(&mut self.head, Link::Empty);
+        while let Link::More(mut node) = cur {
+            cur = mem::replace(&mut node.next, prev);
+            prev = Link::More(node);
+        }
+        self.head = prev;
+    }
+
+    //Link是enum，只能match引用来拿到栈顶元素，不能像Option那样用as_ref
+    pub fn peek(&self) -> Option<&T> {
+        match &self.head {
+            Link::Empty => None,
+            Link::More(node) => Some(&node.elem),
+        }
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        match &mut self.head {
+            Link::Empty => None,
+            Link::More(node) => Some(&mut node.elem),
+        }
+    }
+}
+
+//IntoIter直接包住整个链表，每次next就是pop一次，按pop的顺序返回元素的所有权
+pub struct IntoIter<T>(List<T>);
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+//Iter直接持有当前link的引用，next时match它是Empty还是More
+pub struct Iter<'a, T> {
+    next: &'a Link<T>,
+}
+
+impl<T> List<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: &self.head }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            Link::Empty => None,
+            Link::More(node) => {
+                self.next = &node.next;
+                Some(&node.elem)
+            }
+        }
+    }
 }
 
-impl Drop for List {
+//转换成good_stack，pop顺序保持不变：
+//逐个pop再push会把顺序反过来，所以最后再原地reverse一次，全程迭代不递归
+impl<T> From<List<T>> for good_stack::List<T> {
+    fn from(mut list: List<T>) -> Self {
+        let mut converted = good_stack::List::new();
+        while let Some(elem) = list.pop() {
+            converted.push(elem);
+        }
+        converted.reverse();
+        converted
+    }
+}
+
+//和good_stack一样按push的语义：逐个push，最后一个元素成为head，pop顺序和迭代顺序相反
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        for elem in iter {
+            list.push(elem);
+        }
+        list
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl<T> Drop for List<T> {
     //不实现该方法会爆栈，因为Box的drop不是尾递归的
     fn drop(&mut self) {
         //该种实现直接操作Box智能指针
@@ -69,6 +234,35 @@ mod test {
         // Check empty list behaves right
         assert_eq!(list.pop(), None);
 
+        // Populate list
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+
+        // Push some more just to make sure nothing's corrupted
+        list.push(4);
+        list.push(5);
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), Some(4));
+
+        // Check exhaustion
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn strings() {
+        let mut list = List::new();
+
+        // Check empty list behaves right
+        assert_eq!(list.pop(), None);
+
         // Populate list
         list.push("1".to_string());
         list.push("2".to_string());
@@ -103,4 +297,150 @@ fn long_list() {
         list.push(s.clone());
     }
     drop(list);
-}
\ No newline at end of file
+}
+
+#[test]
+fn peek() {
+    let mut list = List::new();
+    assert_eq!(list.peek(), None);
+    assert_eq!(list.peek_mut(), None);
+    list.push("1".to_string()); list.push("2".to_string());
+
+    assert_eq!(list.peek(), Some(&"2".to_string()));
+    if let Some(value) = list.peek_mut() {
+        value.push('!');
+    }
+    assert_eq!(list.peek(), Some(&"2!".to_string()));
+    assert_eq!(list.pop(), Some("2!".to_string()));
+    assert_eq!(list.peek(), Some(&"1".to_string()));
+}
+
+#[test]
+fn iter() {
+    let mut list = List::new();
+    list.push("a".to_string()); list.push("b".to_string()); list.push("c".to_string());
+
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&"c".to_string()));
+    assert_eq!(iter.next(), Some(&"b".to_string()));
+    assert_eq!(iter.next(), Some(&"a".to_string()));
+    assert_eq!(iter.next(), None);
+
+    let seen: Vec<String> = list.iter().cloned().collect();
+    for expected in seen {
+        assert_eq!(list.pop(), Some(expected));
+    }
+}
+
+#[test]
+fn len() {
+    let mut list = List::new();
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+
+    list.push(1); list.push(2); list.push(3);
+    assert_eq!(list.len(), 3);
+    list.pop();
+    assert_eq!(list.len(), 2);
+    list.push(4);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.len(), list.iter().count());
+
+    while list.pop().is_some() {}
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+    list.pop();
+    assert_eq!(list.len(), 0);
+}
+
+#[test]
+fn into_good_stack() {
+    let mut list = List::new();
+    list.push("a".to_string()); list.push("b".to_string()); list.push("c".to_string());
+
+    let mut converted = good_stack::List::from(list);
+    assert_eq!(converted.len(), 3);
+    assert_eq!(converted.pop(), Some("c".to_string()));
+    assert_eq!(converted.pop(), Some("b".to_string()));
+    assert_eq!(converted.pop(), Some("a".to_string()));
+    assert_eq!(converted.pop(), None);
+
+    assert!(good_stack::List::from(List::<i32>::new()).is_empty());
+}
+
+#[test]
+fn drop_depth() {
+    // Sanity check the probe: letting Box drop a chain on its own recurses once per node
+    let mut link = Link::Empty;
+    for elem in 0..3 {
+        link = Link::More(Box::new(Node {
+            elem,
+            _enter: depth_probe::Enter,
+            next: link,
+            _exit: depth_probe::Exit,
+        }));
+    }
+    depth_probe::take_max_depth();
+    drop(link);
+    assert_eq!(depth_probe::take_max_depth(), 3);
+
+    // List's Drop unlinks each node before freeing it, so nesting never goes past one
+    let mut list = List::new();
+    for elem in 0..100_000 {
+        list.push(elem);
+    }
+    depth_probe::take_max_depth();
+    drop(list);
+    assert_eq!(depth_probe::take_max_depth(), 1);
+}
+
+#[test]
+fn reverse() {
+    let mut list: List<String> = List::new();
+    list.reverse();
+    assert!(list.is_empty());
+
+    list.push("a".to_string());
+    list.reverse();
+    assert_eq!(list.peek(), Some(&"a".to_string()));
+    assert_eq!(list.len(), 1);
+
+    list.push("b".to_string()); list.push("c".to_string());
+    list.reverse();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop(), Some("a".to_string()));
+    assert_eq!(list.pop(), Some("b".to_string()));
+    assert_eq!(list.pop(), Some("c".to_string()));
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn from_iter() {
+    let mut list: List<String> = vec!["a", "b", "c"].into_iter().map(String::from).collect();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop(), Some("c".to_string()));
+    assert_eq!(list.pop(), Some("b".to_string()));
+    assert_eq!(list.pop(), Some("a".to_string()));
+    assert_eq!(list.pop(), None);
+
+    let empty: List<String> = std::iter::empty().collect();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn into_iter() {
+    let mut list = List::new();
+    list.push("a".to_string()); list.push("b".to_string()); list.push("c".to_string());
+
+    let iter = list.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.collect::<Vec<_>>(), vec!["c", "b", "a"]);
+
+    let list: List<String> = vec!["x".to_string(), "y".to_string()].into_iter().collect();
+    let mut seen = Vec::new();
+    for s in list {
+        seen.push(s);
+    }
+    assert_eq!(seen, vec!["y", "x"]);
+    assert_eq!(List::<String>::new().into_iter().next(), None);
+}
@@ -0,0 +1,87 @@
+//给good_stack加上长度上限，满了之后try_push把元素原样还给调用方，用来做背压
+use crate::good_stack::List;
+
+pub struct BoundedList<T> {
+    list: List<T>,
+    max_len: usize,
+}
+
+impl<T> BoundedList<T> {
+    pub fn new(max_len: usize) -> Self {
+        BoundedList { list: List::new(), max_len }
+    }
+
+    pub fn try_push(&mut self, elem: T) -> Result<(), T> {
+        if self.list.len() >= self.max_len {
+            return Err(elem);
+        }
+        self.list.push(elem);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.list.peek()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    pub fn remaining_capacity(&self) -> usize {
+        self.max_len - self.list.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BoundedList;
+
+    #[test]
+    fn basics() {
+        let mut list = BoundedList::new(2);
+        assert_eq!(list.remaining_capacity(), 2);
+        assert_eq!(list.try_push(1), Ok(()));
+        assert_eq!(list.try_push(2), Ok(()));
+        assert_eq!(list.remaining_capacity(), 0);
+        assert_eq!(list.len(), 2);
+
+        // Full: the rejected element comes back untouched
+        assert_eq!(list.try_push(3), Err(3));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.peek(), Some(&2));
+
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.remaining_capacity(), 1);
+        assert_eq!(list.try_push(3), Ok(()));
+        assert_eq!(list.try_push(4), Err(4));
+
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.remaining_capacity(), list.max_len());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut list = BoundedList::new(0);
+        assert_eq!(list.try_push("a".to_string()), Err("a".to_string()));
+        assert_eq!(list.peek_mut(), None);
+    }
+}
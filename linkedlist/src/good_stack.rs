@@ -1,23 +1,83 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FusedIterator, Product, Sum};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::ptr;
 
+//List只由Box和usize组成，没有裸指针字段，所以Send/Sync都是自动推导的：
+//T: Send时List<T>是Send，T: Sync时List<T>是Sync；free链上的MaybeUninit<T>对这两个trait的要求和T一样
+//同理List<T>对T是协变的（Box和Option都是协变的），List<&'static str>可以直接当成List<&'a str>用；
+//Iter<'a, T>对'a和T也是协变的，IterMut<'a, T>和&mut T一样对T不变
 pub struct List<T> {
     head: Link<T>,
+    //缓存元素个数，len() 不需要遍历整条链表
+    len: usize,
+    //开启recycle后，pop下来的空节点先挂在这条链上，新节点优先复用，省掉反复的分配和释放；shrink_to_fit才真正释放
+    free: Link<MaybeUninit<T>>,
+    //默认关闭：不开启时pop直接释放节点，链表不会一直占着峰值时的内存
+    recycle: bool,
 }
 
 // 类型别名，type alias
 type Link<T> = Option<Box<Node<T>>>;
 
+//repr(C)保证Node<T>和Node<MaybeUninit<T>>的内存布局完全一致，空节点才能在两者之间来回转换
+#[repr(C)]
 struct Node<T> {
     elem: T,
     next: Link<T>,
 }
 
+impl<T> Node<T> {
+    //把元素移出来，剩下的节点当作空节点返回，堆上的内存保持不动
+    fn vacate(mut node: Box<Node<T>>) -> (T, Box<Node<MaybeUninit<T>>>) {
+        node.next = None;
+        let raw = Box::into_raw(node);
+        // SAFETY: raw来自Box且布局相同（repr(C)，MaybeUninit<T>和T布局相同）；
+        // elem被读出之后只会以MaybeUninit的形式存在，不会被再次drop
+        unsafe {
+            let elem = ptr::read(&(*raw).elem);
+            (elem, Box::from_raw(raw.cast::<Node<MaybeUninit<T>>>()))
+        }
+    }
+
+    //有空节点就直接复用，没有才分配新的
+    fn alloc(free: &mut Link<MaybeUninit<T>>, elem: T, next: Link<T>) -> Box<Node<T>> {
+        match free.take() {
+            Some(mut parked) => {
+                *free = parked.next.take();
+                Node::refill(parked, elem, next)
+            }
+            None => Box::new(Node { elem, next }),
+        }
+    }
+
+    //往空节点里写入元素，重新当作正常节点使用
+    fn refill(mut node: Box<Node<MaybeUninit<T>>>, elem: T, next: Link<T>) -> Box<Node<T>> {
+        node.next = None;
+        node.elem.write(elem);
+        // SAFETY: elem刚刚被初始化，next是None，两种Node布局相同
+        let mut node = unsafe { Box::from_raw(Box::into_raw(node).cast::<Node<T>>()) };
+        node.next = next;
+        node
+    }
+}
+
 //每个集合类型应该实现 3 种迭代器类型：
 
 //IntoIter - T  直接返回所有权
 pub struct IntoIter<T>(List<T>);
 
-impl<T> List<T> {
-    pub fn into_iter(self) -> IntoIter<T> {
+//实现IntoIterator后才能直接写 for x in list，list.into_iter()也走这里
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
         IntoIter(self)
     }
 }
@@ -28,15 +88,35 @@ impl<T> Iterator for IntoIter<T> {
         // access fields of a tuple struct numerically
         self.0.pop()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
 }
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+//pop在空链表上总是返回None
+impl<T> FusedIterator for IntoIter<T> {}
 //IterMut - &mut T  返回可变引用
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
+    //还没有返回的元素个数
+    len: usize,
 }
 
 impl<T> List<T> {
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        IterMut { next: self.head.as_deref_mut() }
+        IterMut { next: self.head.as_deref_mut(), len: self.len }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
@@ -46,27 +126,59 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.next.take().map(|node| {
             self.next = node.next.as_deref_mut();
+            self.len -= 1;
             &mut node.elem
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+//next被take成None之后不会再被赋值
+impl<T> FusedIterator for IterMut<'_, T> {}
+
 //Iter - &T  返回不可变引用
 //这里的基本逻辑是我们持有一个当前节点的指针，当生成一个值后，该指针将指向下一个节点。
 //iter中的next至少要比iter活的更长（源比派生要活得长, 属性比结构体要活得长）
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    //单链表没法从尾部往回走，第一次调用next_back时把剩下的元素引用全部收集到这里
+    back: VecDeque<&'a T>,
+    //还没有返回的元素个数（包括缓冲区里的）
+    len: usize,
 }
 
 impl<T> List<T> {
     // pub fn iter<'a>(&'a self) -> Iter<'a, T> {
     // 生命消除规则，只有一个参数或有&self时，输出生命周期自动标注
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         // 这里我们为 `iter` 声明一个生命周期 'a , 此时 `&self` 需要至少和 `Iter` 活得一样久
         //self.head.as_ref().map(|node| &**node)
         //self.head.as_ref().map::<&Node<T>, _>(|node| &node)
         // as_deref() 从 Option<T> (或 &Option<T>) 转换为 Option<&T::Target>
-        Iter { next: self.head.as_deref() }
+        Iter { next: self.head.as_deref(), back: VecDeque::new(), len: self.len }
+    }
+
+    //先把next指针往下移start个节点再返回迭代器，start越界时得到空迭代器；最多走len步
+    pub fn iter_from(&self, start: usize) -> Iter<'_, T> {
+        let mut next = self.head.as_deref();
+        for _ in 0..start.min(self.len) {
+            next = next.and_then(|node| node.next.as_deref());
+        }
+        Iter { next, back: VecDeque::new(), len: self.len.saturating_sub(start) }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
@@ -74,36 +186,232 @@ impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.map(|node| {
+        let elem = match self.next {
+            Some(node) => {
+                self.next = node.next.as_deref();
+                Some(&node.elem)
+            }
+            //已经收集过，从缓冲区前端取
+            None => self.back.pop_front(),
+        };
+        if elem.is_some() {
+            self.len -= 1;
+        }
+        elem
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+//next为None且缓冲区为空之后，两端都不会再产生元素
+impl<T> FusedIterator for Iter<'_, T> {}
+
+//rev()需要额外O(n)的时间和空间：第一次next_back会遍历剩余节点并缓存它们的引用
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.next {
             self.next = node.next.as_deref();
-            &node.elem
-        })
+            self.back.push_back(&node.elem);
+        }
+        let elem = self.back.pop_back();
+        if elem.is_some() {
+            self.len -= 1;
+        }
+        elem
+    }
+}
+
+//ExtractIf - 边遍历边把满足条件的节点摘下来，返回T的所有权
+//没有被消费到的部分（包括迭代器提前drop时）原样留在链表里
+pub struct ExtractIf<'a, T, F> {
+    link: Option<&'a mut Link<T>>,
+    len: &'a mut usize,
+    pred: F,
+}
+
+impl<T> List<T> {
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, F> {
+        ExtractIf { link: Some(&mut self.head), len: &mut self.len, pred }
+    }
+}
+
+impl<T, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let link = self.link.take()?;
+            let matched = match link.as_deref_mut() {
+                None => return None,
+                Some(node) => (self.pred)(&mut node.elem),
+            };
+            if matched {
+                let mut node = link.take().unwrap();
+                *link = node.next.take();
+                *self.len -= 1;
+                self.link = Some(link);
+                return Some(node.elem);
+            }
+            self.link = Some(&mut link.as_mut().unwrap().next);
+        }
+    }
+}
+
+//PeekMut - 类似BinaryHeap::peek_mut，守卫解引用到head元素，drop时是检查链表不变量的地方。
+//普通栈没有需要维护的顺序，所以drop现在什么也不做；以后有序的变体可以在这里重新调整位置
+pub struct PeekMut<'a, T> {
+    //守卫只在链表非空时创建，所以head一定是Some
+    list: &'a mut List<T>,
+}
+
+impl<T> List<T> {
+    pub fn peek_mut_guard(&mut self) -> Option<PeekMut<'_, T>> {
+        if self.head.is_some() {
+            Some(PeekMut { list: self })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Deref for PeekMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.list.head.as_ref().unwrap().elem
+    }
+}
+
+impl<T> DerefMut for PeekMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.list.head.as_mut().unwrap().elem
+    }
+}
+
+impl<T> Drop for PeekMut<'_, T> {
+    fn drop(&mut self) {
+        //目前没有需要重新检查的不变量
+    }
+}
+
+//CursorMut - 停在某个节点上，可以在遍历过程中原地插入、删除，不需要重新从head走
+//link指向"持有当前节点的那个link"，current为None表示已经走到末尾
+pub struct CursorMut<'a, T> {
+    link: Option<&'a mut Link<T>>,
+    len: &'a mut usize,
+    free: &'a mut Link<MaybeUninit<T>>,
+}
+
+impl<T> List<T> {
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { link: Some(&mut self.head), len: &mut self.len, free: &mut self.free }
+    }
+}
+
+impl<T> CursorMut<'_, T> {
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.link.as_mut()?.as_mut().map(|node| &mut node.elem)
+    }
+
+    //移到下一个节点，已经在末尾时保持不动
+    pub fn move_next(&mut self) {
+        if let Some(link) = self.link.take() {
+            if link.is_some() {
+                self.link = Some(&mut link.as_mut().unwrap().next);
+            } else {
+                self.link = Some(link);
+            }
+        }
+    }
+
+    //插在当前节点后面，cursor不动；在末尾时新节点就放在当前位置并成为current
+    pub fn insert_after(&mut self, elem: T) {
+        if let Some(link) = self.link.as_mut() {
+            match link.as_mut() {
+                Some(node) => {
+                    let next = node.next.take();
+                    node.next = Some(Node::alloc(self.free, elem, next));
+                }
+                None => **link = Some(Node::alloc(self.free, elem, None)),
+            }
+            *self.len += 1;
+        }
+    }
+
+    //删除当前节点并返回它的元素，之后current变成原来的下一个节点
+    pub fn remove_current(&mut self) -> Option<T> {
+        let link = self.link.as_mut()?;
+        let mut node = link.take()?;
+        **link = node.next.take();
+        *self.len -= 1;
+        Some(node.elem)
     }
 }
 
 impl<T> List<T> {
     pub fn new() -> Self {
-        List { head: None }
+        List { head: None, len: 0, free: None, recycle: false }
+    }
+
+    //开启节点复用的空链表，适合反复push/pop的场景；clone和split_off出来的链表沿用同样的设置
+    pub fn with_recycling() -> Self {
+        List { head: None, len: 0, free: None, recycle: true }
     }
 
     pub fn push(&mut self, elem: T) {
-        let new_node = Box::new(Node {
-            elem: elem,
-            //take方法可以拿到option的所有权，从选项中取出值，将 None 留在其位置。
-            next: self.head.take(),
-        });
+        //take方法可以拿到option的所有权，从选项中取出值，将 None 留在其位置。
+        let next = self.head.take();
+        let new_node = Node::alloc(&mut self.free, elem, next);
 
         self.head = Some(new_node);
+        self.len += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
         //这里也需要拿到所有权
-        self.head.take().map(|node| {
-            self.head = node.next;
-            node.elem
+        self.head.take().map(|mut node| {
+            self.head = node.next.take();
+            self.len -= 1;
+            if !self.recycle {
+                return node.elem;
+            }
+            //节点不释放，挂到空节点链上留给下一次push
+            let (elem, mut parked) = Node::vacate(node);
+            parked.next = self.free.take();
+            self.free = Some(parked);
+            elem
         })
     }
 
+    //释放pop后留下的空节点；和clear一样迭代释放，避免长链递归drop爆栈
+    pub fn shrink_to_fit(&mut self) {
+        let mut cur_link = self.free.take();
+        while let Some(mut boxed_node) = cur_link {
+            cur_link = boxed_node.next.take();
+        }
+    }
+
+    //只有head满足f时才pop，否则链表保持不变
+    pub fn pop_if<F: FnOnce(&T) -> bool>(&mut self, f: F) -> Option<T> {
+        if f(self.peek()?) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn peek(&self) -> Option<&T> {
         //self作为参数会传递所有权，如此就不能仅返回T的引用了,因为self会被释放
         //map作用在self.head中会拿到所有权，离开作用域会被释放，不能返回本地变量的引用
@@ -120,11 +428,190 @@ impl<T> List<T> {
             &mut node.elem
         })
     }
-}
 
-impl<T> Drop for List<T> {
-    //不实现该方法会爆栈，因为Box的drop不是尾递归的
-    fn drop(&mut self) {
+    //从head往下数第n个元素，peek_nth(0)等价于peek()
+    pub fn peek_nth(&self, n: usize) -> Option<&T> {
+        self.get(n)
+    }
+
+    //下标0是head（最后push的元素），越界返回None
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
+    }
+
+    //按pop顺序依次返回相邻的两个元素 (a, b)，少于两个元素时什么都不返回
+    //闭包里只保存前一个节点，每次取它和它的next，然后一起往后挪一格
+    pub fn pairs(&self) -> impl Iterator<Item = (&T, &T)> {
+        let mut first = self.head.as_deref();
+        core::iter::from_fn(move || {
+            let a = first?;
+            let b = a.next.as_deref()?;
+            first = Some(b);
+            Some((&a.elem, &b.elem))
+        })
+    }
+
+    //按pop顺序每n个元素一组，最后一组可能不足n个；n为0时panic
+    pub fn chunks(&self, n: usize) -> impl Iterator<Item = Vec<&T>> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut iter = self.iter();
+        core::iter::from_fn(move || {
+            let chunk: Vec<&T> = iter.by_ref().take(n).collect();
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+    }
+
+    //和chunks一样按pop顺序分组，但按尾部对齐：不足n个的那组放在最前面。
+    //len是缓存的，第一组的大小直接用len % n算出来，不需要先把所有引用收集到缓冲区里
+    pub fn rchunks(&self, n: usize) -> impl Iterator<Item = Vec<&T>> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut iter = self.iter();
+        let mut size = match self.len % n {
+            0 => n,
+            short => short,
+        };
+        core::iter::from_fn(move || {
+            let chunk: Vec<&T> = iter.by_ref().take(size).collect();
+            size = n;
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+    }
+
+    //一直走到最后一个节点，也就是最早push的元素
+    pub fn last(&self) -> Option<&T> {
+        let mut node = self.head.as_deref()?;
+        while let Some(next) = node.next.as_deref() {
+            node = next;
+        }
+        Some(&node.elem)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        let mut node = self.head.as_deref_mut()?;
+        while node.next.is_some() {
+            node = node.next.as_deref_mut().unwrap();
+        }
+        Some(&mut node.elem)
+    }
+
+    //n为0时是tail（最早push的元素），用缓存的长度换算成从head开始的下标
+    pub fn nth_from_tail(&self, n: usize) -> Option<&T> {
+        //分两步减，n为usize::MAX时n + 1不会溢出
+        let index = self.len.checked_sub(n)?.checked_sub(1)?;
+        self.get(index)
+    }
+
+    //第一个满足pred的元素的下标，找到就停止
+    pub fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    pub fn find<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<&T> {
+        self.iter().find(|elem| pred(elem))
+    }
+
+    //都会短路：all遇到第一个false就返回，any遇到第一个true就返回；空链表all为true，any为false
+    pub fn all<F: FnMut(&T) -> bool>(&self, f: F) -> bool {
+        self.iter().all(f)
+    }
+
+    pub fn any<F: FnMut(&T) -> bool>(&self, f: F) -> bool {
+        self.iter().any(f)
+    }
+
+    //一次遍历数出满足f的元素个数，直接在循环里计数，不经过filter适配器
+    pub fn count_if<F: FnMut(&T) -> bool>(&self, mut f: F) -> usize {
+        let mut count = 0;
+        for elem in self.iter() {
+            if f(elem) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    //按pop顺序累积，空链表直接返回init
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    //和fold一样按pop顺序累积，但f返回Err时立刻停下并把这个Err返回
+    pub fn try_fold<B, E, F: FnMut(B, &T) -> Result<B, E>>(&self, init: B, f: F) -> Result<B, E> {
+        self.iter().try_fold(init, f)
+    }
+
+    //类似前缀和：每一步的累积值组成一条新链表，长度和self一样，按pop顺序排列
+    pub fn running_fold<B: Clone, F: FnMut(&B, &T) -> B>(&self, init: B, mut f: F) -> List<B> {
+        let mut acc = init;
+        let mut folded = List::new();
+        let mut tail = &mut folded.head;
+        for elem in self.iter() {
+            acc = f(&acc, elem);
+            tail = &mut tail.insert(Node::alloc(&mut folded.free, acc.clone(), None)).next;
+        }
+        folded.len = self.len;
+        folded
+    }
+
+    //原地翻转：逐个摘下节点，把它的next指向已翻转部分，不分配新节点
+    pub fn reverse(&mut self) {
+        let mut prev: Link<T> = None;
+        let mut cur_link = self.head.take();
+
+        while let Some(mut boxed_node) = cur_link {
+            cur_link = boxed_node.next.take();
+            boxed_node.next = prev;
+            prev = Some(boxed_node);
+        }
+        self.head = prev;
+    }
+
+    //返回链表末尾那个为None的link，可以直接在后面挂节点
+    fn tail_link(&mut self) -> &mut Link<T> {
+        let mut link = &mut self.head;
+        while let Some(node) = link {
+            link = &mut node.next;
+        }
+        link
+    }
+
+    //返回指向第index个节点的link，index超出长度时停在末尾的None
+    fn link_at(&mut self, index: usize) -> &mut Link<T> {
+        Self::nth_link(&mut self.head, index)
+    }
+
+    //从任意一条link开始往后走n步，sort这类直接操作节点链的地方也要用
+    fn nth_link(mut link: &mut Link<T>, n: usize) -> &mut Link<T> {
+        for _ in 0..n {
+            match link {
+                Some(node) => link = &mut node.next,
+                None => break,
+            }
+        }
+        link
+    }
+
+    //把other的整条链挂到self的末尾，不clone元素，other变为空链表
+    pub fn append(&mut self, other: &mut List<T>) {
+        *self.tail_link() = other.head.take();
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    //和Drop共用的迭代释放逻辑，清空后链表仍可继续使用
+    pub fn clear(&mut self) {
         //该种实现直接操作Box智能指针
         let mut cur_link = self.head.take();
 
@@ -132,92 +619,701 @@ impl<T> Drop for List<T> {
             //cur_link拿到所有权后，超出作用范围自动drop
             cur_link = boxed_node.next.take();
         }
+        self.len = 0;
 
         // //该种实现方法在element实现copy trait时需要拷贝每个node值，如果数据量过大性能会差
         // while let Some(_) = self.pop() {}
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::List;
+    //只保留f返回true的元素，其余节点就地摘除并释放
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut link = &mut self.head;
+        //先判断再决定是前进还是摘除，避免在同一个match里既借用又修改link
+        while let Some(node) = link.as_deref() {
+            if f(&node.elem) {
+                link = &mut link.as_mut().unwrap().next;
+            } else {
+                //摘下当前节点，把它的next接到link上，node离开作用域时释放
+                let mut node = link.take().unwrap();
+                *link = node.next.take();
+                self.len -= 1;
+            }
+        }
+    }
 
-    #[test]
-    fn basics() {
-        let mut list = List::new();
+    //按位置两两配对，较短的一边用完就停，较长一边剩下的元素随self/other一起释放
+    pub fn zip<U>(mut self, mut other: List<U>) -> List<(T, U)> {
+        let mut zipped = List::new();
+        let mut tail = &mut zipped.head;
+        let mut len = 0;
+        while let (Some(a), Some(b)) = (self.pop(), other.pop()) {
+            tail = &mut tail.insert(Node::alloc(&mut zipped.free, (a, b), None)).next;
+            len += 1;
+        }
+        zipped.len = len;
+        zipped
+    }
 
-        // Check empty list behaves right
-        assert_eq!(list.pop(), None);
+    //只保留前len个元素，多出来的部分立即释放（split_off出来的链表drop时也是迭代释放）
+    pub fn truncate(&mut self, len: usize) {
+        drop(self.split_off(len));
+    }
 
-        // Populate list
-        list.push("1".to_string());
-        list.push("2".to_string());
-        list.push("3".to_string());
+    //按原顺序把每个元素转换成U，新节点挂在新链表末尾，不递归
+    pub fn map_into<U, F: FnMut(T) -> U>(mut self, mut f: F) -> List<U> {
+        let len = self.len;
+        let mut mapped = List::new();
+        let mut tail = &mut mapped.head;
+        while let Some(elem) = self.pop() {
+            let node = tail.insert(Node::alloc(&mut mapped.free, f(elem), None));
+            tail = &mut node.next;
+        }
+        mapped.len = len;
+        mapped
+    }
 
-        // Check normal removal
-        assert_eq!(list.pop(), Some("3".to_string()));
-        assert_eq!(list.pop(), Some("2".to_string()));
+    //在第index个位置插入新节点，0就是新的head；index超过长度时直接挂在末尾
+    pub fn insert(&mut self, index: usize, elem: T) {
+        let link = Self::nth_link(&mut self.head, index);
+        let next = link.take();
+        *link = Some(Node::alloc(&mut self.free, elem, next));
+        self.len += 1;
+    }
 
-        // Push some more just to make sure nothing's corrupted
-        list.push("4".to_string());
-        list.push("5".to_string());
+    //摘下第index个节点并返回它的元素，前后节点重新连上；越界返回None
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let link = self.link_at(index);
+        let mut node = link.take()?;
+        *link = node.next.take();
+        self.len -= 1;
+        Some(node.elem)
+    }
 
-        // Check normal removal
-        assert_eq!(list.pop(), Some("5".to_string()));
-        assert_eq!(list.pop(), Some("4".to_string()));
+    //交换两个位置上的值（不移动节点）。IterMut给出的可变引用互不重叠，一次遍历就能拿到两个
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(
+            i < self.len && j < self.len,
+            "swap index out of bounds: the len is {} but the indices are {} and {}",
+            self.len, i, j
+        );
+        if i == j {
+            return;
+        }
+        let (lo, hi) = (i.min(j), i.max(j));
+        let mut iter = self.iter_mut();
+        let a = iter.nth(lo).unwrap();
+        let b = iter.nth(hi - lo - 1).unwrap();
+        mem::swap(a, b);
+    }
 
-        // Check exhaustion
-        assert_eq!(list.pop(), Some("1".to_string()));
-        assert_eq!(list.pop(), None);
+    //把前n个元素整段挪到末尾，相对顺序不变；n对长度取模，只改指针不拷贝
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 || n.is_multiple_of(self.len) {
+            return;
+        }
+        let mut front = self.head.take();
+        self.head = Self::nth_link(&mut front, n % self.len).take();
+        *self.tail_link() = front;
     }
-}
 
-#[test]
-fn peek() {
-    let mut list = List::new();
-    assert_eq!(list.peek(), None);
-    assert_eq!(list.peek_mut(), None);
-    list.push(1); list.push(2); list.push(3);
+    //按f把节点分到两条链表里（返回 (满足的, 不满足的)），直接挪动节点，各自保持原来的相对顺序
+    pub fn partition<F: FnMut(&T) -> bool>(mut self, mut f: F) -> (List<T>, List<T>) {
+        let mut matched = List::new();
+        let mut rest = List::new();
+        let mut matched_tail = &mut matched.head;
+        let mut rest_tail = &mut rest.head;
+        let (mut matched_len, mut rest_len) = (0, 0);
 
-    assert_eq!(list.peek(), Some(&3));
-    assert_eq!(list.peek_mut(), Some(&mut 3));
+        let mut cur_link = self.head.take();
+        while let Some(mut boxed_node) = cur_link {
+            cur_link = boxed_node.next.take();
+            if f(&boxed_node.elem) {
+                matched_tail = &mut matched_tail.insert(boxed_node).next;
+                matched_len += 1;
+            } else {
+                rest_tail = &mut rest_tail.insert(boxed_node).next;
+                rest_len += 1;
+            }
+        }
+        matched.len = matched_len;
+        rest.len = rest_len;
+        (matched, rest)
+    }
 
-    list.peek_mut().map(|value| {
-        *value = 42
-    });
+    //从head开始连续满足f的那一段，遇到第一个不满足的就停下；后面的部分被释放
+    //和drop_while用同一个f时，两者正好把链表切成前缀和剩余两段，节点都是直接挪过去的
+    pub fn take_while<F: FnMut(&T) -> bool>(mut self, mut f: F) -> List<T> {
+        let n = self.position(|elem| !f(elem)).unwrap_or(self.len);
+        self.truncate(n);
+        self
+    }
 
-    assert_eq!(list.peek(), Some(&42));
-    assert_eq!(list.pop(), Some(42));
+    //去掉开头连续满足f的那一段，返回剩下的部分
+    pub fn drop_while<F: FnMut(&T) -> bool>(mut self, mut f: F) -> List<T> {
+        let n = self.position(|elem| !f(elem)).unwrap_or(self.len);
+        self.split_off(n)
+    }
+
+    //self保留前at个元素，剩下的节点直接移动到返回的新链表中
+    pub fn split_off(&mut self, at: usize) -> List<T> {
+        if at >= self.len {
+            return List::new();
+        }
+        let rest = self.link_at(at).take();
+        let rest_len = self.len - at;
+        self.len = at;
+        List { head: rest, len: rest_len, free: None, recycle: self.recycle }
+    }
 }
 
-#[test]
-fn into_iter() {
-    let mut list = List::new();
-    list.push(1); list.push(2); list.push(3);
+//和Vec互转时 Vec[0] 对应head，即Vec的顺序就是pop的顺序，来回转换顺序不变
+//注意这和collect()不同，collect()是按push语义把最后一个元素放在head
+impl<T> From<Vec<T>> for List<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().rev().collect()
+    }
+}
 
-    let mut iter = list.into_iter();
-    assert_eq!(iter.next(), Some(3));
-    assert_eq!(iter.next(), Some(2));
-    assert_eq!(iter.next(), Some(1));
-    assert_eq!(iter.next(), None);
+impl<T> From<List<T>> for Vec<T> {
+    fn from(list: List<T>) -> Self {
+        list.into_iter().collect()
+    }
 }
 
-#[test]
-fn iter_mut() {
-    let mut list = List::new();
-    list.push(1); list.push(2); list.push(3);
+//从切片构造时允许的最大长度，超过就拒绝，防止意外把巨大的输入整个拷贝进链表
+pub const MAX_FROM_SLICE: usize = 1_000_000;
 
-    let mut iter = list.iter_mut();
-    assert_eq!(iter.next(), Some(&mut 3));
-    assert_eq!(iter.next(), Some(&mut 2));
-    assert_eq!(iter.next(), Some(&mut 1));
+//切片超过MAX_FROM_SLICE时的错误，带上实际长度
+#[derive(Debug, PartialEq, Eq)]
+pub struct SliceTooLong(pub usize);
+
+impl fmt::Display for SliceTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "slice of length {} exceeds the limit of {}", self.0, MAX_FROM_SLICE)
+    }
 }
 
+//Error在std里，关掉std时只保留Display
+#[cfg(feature = "std")]
+impl std::error::Error for SliceTooLong {}
 
-#[test]
-fn iter() {
-    let mut list = List::new();
-    list.push(1); list.push(2); list.push(3);
+//顺序和From<Vec<T>>一样：slice[0]成为head
+impl<T: Clone> TryFrom<&[T]> for List<T> {
+    type Error = SliceTooLong;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        if slice.len() > MAX_FROM_SLICE {
+            return Err(SliceTooLong(slice.len()));
+        }
+        Ok(slice.iter().rev().cloned().collect())
+    }
+}
+
+//list![a, b, c] 构造head为a的链表，pop顺序为a, b, c（与From<Vec<T>>一致）
+#[macro_export]
+macro_rules! list {
+    () => {
+        $crate::good_stack::List::new()
+    };
+    //用数组而不是vec!，no_std下也能用；FromIterator写全路径，2015/2018版本的crate里它不在prelude中
+    ($($elem:expr),+ $(,)?) => {
+        <$crate::good_stack::List<_> as ::core::iter::FromIterator<_>>::from_iter(
+            ::core::iter::IntoIterator::into_iter([$($elem),+]).rev(),
+        )
+    };
+}
+
+impl<T> List<T> {
+    pub fn into_vec(self) -> Vec<T> {
+        self.into()
+    }
+}
+
+impl<T: Clone> List<T> {
+    //按pop顺序clone到Vec里，用缓存的长度一次分配好容量
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend(self.iter().cloned());
+        vec
+    }
+
+    //按切片顺序逐个push，所以切片的最后一个元素成为head，pop顺序和切片顺序相反；
+    //每挂上一个节点就把len加一，clone中途panic时已经挂上的节点也都算在len里
+    pub fn push_slice(&mut self, items: &[T]) {
+        for elem in items {
+            let elem = elem.clone();
+            let next = self.head.take();
+            self.head = Some(Node::alloc(&mut self.free, elem, next));
+            self.len += 1;
+        }
+    }
+
+    //clone出每个元素交给标准库的sum/product，空链表得到各自的单位元
+    pub fn sum<S: Sum<T>>(&self) -> S {
+        self.iter().cloned().sum()
+    }
+
+    pub fn product<P: Product<T>>(&self) -> P {
+        self.iter().cloned().product()
+    }
+
+    //以第一个元素的clone作为初始值，空链表返回None
+    pub fn reduce<F: FnMut(&T, &T) -> T>(&self, mut f: F) -> Option<T> {
+        let mut iter = self.iter();
+        let first = iter.next()?.clone();
+        Some(iter.fold(first, |acc, elem| f(&acc, elem)))
+    }
+}
+
+impl<T> List<List<T>> {
+    //把每条内层链表的节点依次接起来，不clone元素；tail只沿新接上的那一段往后走，整体O(n)
+    pub fn flatten(mut self) -> List<T> {
+        let mut flat = List::new();
+        let mut tail = &mut flat.head;
+        let mut len = 0;
+        while let Some(mut inner) = self.pop() {
+            let inner_len = inner.len;
+            *tail = inner.head.take();
+            inner.len = 0;
+            tail = List::nth_link(tail, inner_len);
+            len += inner_len;
+        }
+        flat.len = len;
+        flat
+    }
+}
+
+//合并时从链表上摘下来的几段节点都放在这里。用户的Ord可能panic，守卫drop时把它们按merged、a、b、rest的顺序
+//接回list.head，一个元素都不丢，len也就不用改；正常结束时只剩一段非空，空的段直接跳过，不用遍历
+struct Detached<'a, T> {
+    list: &'a mut List<T>,
+    merged: Link<T>,
+    a: Link<T>,
+    b: Link<T>,
+    rest: Link<T>,
+}
+
+impl<T> Drop for Detached<'_, T> {
+    fn drop(&mut self) {
+        let mut head = self.merged.take();
+        let mut tail = &mut head;
+        for piece in [&mut self.a, &mut self.b, &mut self.rest] {
+            if piece.is_none() {
+                continue;
+            }
+            while let Some(node) = tail {
+                tail = &mut node.next;
+            }
+            *tail = piece.take();
+        }
+        self.list.head = head;
+    }
+}
+
+impl<T: Ord> List<T> {
+    //把a、b两条升序链表合并后挂到tail上，只重新连接节点；相等时a的节点排在前面，保证稳定。
+    //比较的时候每个节点都在a、b或tail这条链上，比较panic时交给调用方的Detached接回去
+    fn merge_into(mut tail: &mut Link<T>, a: &mut Link<T>, b: &mut Link<T>) {
+        while let (Some(x), Some(y)) = (a.as_deref(), b.as_deref()) {
+            let src = if y.elem < x.elem { &mut *b } else { &mut *a };
+            let mut node = src.take().unwrap();
+            *src = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        //剩下的一条直接整段接上
+        *tail = a.take().or(b.take());
+    }
+
+    //自底向上的归并排序：每一轮把链表切成长度为width的段，两两合并，width翻倍直到覆盖整条链表
+    //没有递归，栈深度和链表长度无关；merge_into在相等时保留前一段的节点，所以排序是稳定的
+    //每一轮从rest切出a、b两段合并到merged末尾，所有段都放在Detached里，比较panic时整条链会被接回去
+    pub fn sort(&mut self) {
+        let len = self.len;
+        let rest = self.head.take();
+        let mut detached = Detached { list: self, merged: None, a: None, b: None, rest };
+        let mut width = 1;
+        while width < len {
+            let mut tail = &mut detached.merged;
+            while detached.rest.is_some() {
+                detached.a = detached.rest.take();
+                detached.b = Self::nth_link(&mut detached.a, width).take();
+                detached.rest = Self::nth_link(&mut detached.b, width).take();
+                Self::merge_into(tail, &mut detached.a, &mut detached.b);
+                tail = Self::nth_link(tail, 2 * width);
+            }
+            detached.rest = detached.merged.take();
+            width *= 2;
+        }
+    }
+
+    //相等时都返回第一次出现的元素（标准库的max在相等时返回最后一个，所以这里自己比较）
+    pub fn min(&self) -> Option<&T> {
+        self.iter().reduce(|best, elem| if elem < best { elem } else { best })
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.iter().reduce(|best, elem| if elem > best { elem } else { best })
+    }
+
+    //self和other都按pop顺序升序排列，合并后self仍然有序，other被消费
+    pub fn merge_sorted(&mut self, mut other: List<T>) {
+        self.len += other.len;
+        other.len = 0;
+        let a = self.head.take();
+        let b = other.head.take();
+        let mut detached = Detached { list: self, merged: None, a, b, rest: None };
+        Self::merge_into(&mut detached.merged, &mut detached.a, &mut detached.b);
+    }
+}
+
+impl<T: PartialEq> List<T> {
+    pub fn contains(&self, target: &T) -> bool {
+        //any遇到第一个匹配就返回
+        self.iter().any(|elem| elem == target)
+    }
+
+    //和Vec::dedup一样只去掉连续重复的元素，每段保留第一个；一次遍历，重复节点直接摘掉释放
+    pub fn dedup(&mut self) {
+        let mut cur = self.head.as_deref_mut();
+        while let Some(node) = cur {
+            //先比较再摘节点，==中途panic时链表还是完整的
+            while node.next.as_ref().is_some_and(|next| next.elem == node.elem) {
+                let mut dup = node.next.take().unwrap();
+                node.next = dup.next.take();
+                self.len -= 1;
+            }
+            cur = node.next.as_deref_mut();
+        }
+    }
+}
+
+//list[i]，0是head，越界时panic
+impl<T> Index<usize> for List<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let len = self.len;
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {} but the index is {}", len, index)
+        })
+    }
+}
+
+impl<T> IndexMut<usize> for List<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len;
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {} but the index is {}", len, index)
+        })
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+//collect() 按迭代顺序依次push，和连续调用push的语义一致：
+//最后一个元素成为head，pop的顺序与迭代顺序相反
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        for elem in iter {
+            list.push(elem);
+        }
+        list
+    }
+}
+
+//extend同样是逐个push，原有元素保留在新元素下面
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<'a, T: Clone> Extend<&'a T> for List<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+//derive(Clone)会递归clone整条Box链，长链表同样会爆栈
+//这里用一个指向新链表末尾next的可变引用，逐个挂上新节点
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        let mut new_list = List::new();
+        new_list.recycle = self.recycle;
+        let mut tail = &mut new_list.head;
+        for elem in self.iter() {
+            let node = tail.insert(Node::alloc(&mut new_list.free, elem.clone(), None));
+            tail = &mut node.next;
+        }
+        new_list.len = self.len;
+        new_list
+    }
+
+    //默认的clone_from会先drop掉self再整条重新分配；这里先在已有节点上逐个clone_from覆盖，
+    //self更长就截掉多余的部分，更短才为剩下的元素申请节点（优先用pop留下的空节点）
+    fn clone_from(&mut self, source: &Self) {
+        let mut src = source.iter();
+        for (dst, elem) in self.iter_mut().zip(&mut src) {
+            dst.clone_from(elem);
+        }
+        if self.len >= source.len {
+            self.truncate(source.len);
+            return;
+        }
+
+        //只借用head，这样每挂上一个节点就能更新len，clone中途panic时len和实际节点数仍然一致
+        let mut tail = Self::nth_link(&mut self.head, self.len);
+        for elem in src {
+            tail = &mut tail.insert(Node::alloc(&mut self.free, elem.clone(), None)).next;
+            self.len += 1;
+        }
+    }
+}
+
+//按pop顺序输出，形如 [3, 2, 1]
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+//给用户看的格式：按pop顺序用 -> 连接，空链表输出(empty)，直接写进formatter不拼中间String
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.iter();
+        match iter.next() {
+            None => write!(f, "(empty)"),
+            Some(first) => {
+                write!(f, "{}", first)?;
+                for elem in iter {
+                    write!(f, " -> {}", elem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+//先比较缓存的长度，再逐个元素比较，遇到第一个不同的元素立即返回
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+//和切片/Vec比较时按pop顺序对应：slice[0]对应head，和From<Vec<T>>、into_vec的约定一致
+impl<T: PartialEq> PartialEq<[T]> for List<T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for List<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self == other[..]
+    }
+}
+
+//和PartialEq保持一致：先hash长度再按iter()顺序hash每个元素，
+//长度作为前缀可以避免不同切分方式拼起来的序列产生相同的hash输入
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+//序列化为iter()顺序（head在前）的序列，反序列化时用From<Vec<T>>还原，保证来回转换顺序不变
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for List<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for List<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(List::from)
+    }
+}
+
+impl<T> Drop for List<T> {
+    //不实现该方法会爆栈，因为Box的drop不是尾递归的
+    fn drop(&mut self) {
+        self.clear();
+        self.shrink_to_fit();
+    }
+}
+
+//只在关闭std时编译，cargo build --no-default-features 能通过就说明核心API不依赖std
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
+fn no_std_api() {
+    let mut list: List<i32> = (0..3).collect();
+    list.push(3);
+    list.extend([4, 5]);
+    let _ = list.peek();
+    let _ = list.iter().rev().count();
+    let _ = list.clone() == list;
+    let _: Vec<i32> = list.into_vec();
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+
+        // Check empty list behaves right
+        assert_eq!(list.pop(), None);
+
+        // Populate list
+        list.push("1".to_string());
+        list.push("2".to_string());
+        list.push("3".to_string());
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some("3".to_string()));
+        assert_eq!(list.pop(), Some("2".to_string()));
+
+        // Push some more just to make sure nothing's corrupted
+        list.push("4".to_string());
+        list.push("5".to_string());
+
+        // Check normal removal
+        assert_eq!(list.pop(), Some("5".to_string()));
+        assert_eq!(list.pop(), Some("4".to_string()));
+
+        // Check exhaustion
+        assert_eq!(list.pop(), Some("1".to_string()));
+        assert_eq!(list.pop(), None);
+    }
+}
+
+//每个元素都持有同一个Rc，alive()就是还没被drop的元素个数，用来检查节点摘除时元素被及时释放
+#[cfg(test)]
+struct DropTracker(std::rc::Rc<()>);
+
+#[cfg(test)]
+impl DropTracker {
+    fn new() -> Self {
+        DropTracker(std::rc::Rc::new(()))
+    }
+
+    fn elem(&self) -> std::rc::Rc<()> {
+        self.0.clone()
+    }
+
+    fn list(&self, n: usize) -> List<std::rc::Rc<()>> {
+        (0..n).map(|_| self.elem()).collect()
+    }
+
+    fn alive(&self) -> usize {
+        std::rc::Rc::strong_count(&self.0) - 1
+    }
+}
+
+//比较时只要有一边是负数就panic，用来检查排序、合并、去重中途失败后元素一个都不丢
+#[cfg(test)]
+#[derive(Debug, Eq)]
+struct PanicOnCmp(i32);
+
+#[cfg(test)]
+impl PartialEq for PanicOnCmp {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+#[cfg(test)]
+impl Ord for PanicOnCmp {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        assert!(self.0 >= 0 && other.0 >= 0, "compared {} with {}", self.0, other.0);
+        self.0.cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+impl PartialOrd for PanicOnCmp {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+//clone时对负数panic，用来检查clone中途失败后链表仍然保持一致
+#[cfg(test)]
+#[derive(Debug, PartialEq)]
+struct PanicOnClone(i32);
+
+#[cfg(test)]
+impl Clone for PanicOnClone {
+    fn clone(&self) -> Self {
+        assert!(self.0 >= 0, "clone of {}", self.0);
+        PanicOnClone(self.0)
+    }
+}
+
+#[test]
+#[allow(clippy::option_map_unit_fn)]
+fn peek() {
+    let mut list = List::new();
+    assert_eq!(list.peek(), None);
+    assert_eq!(list.peek_mut(), None);
+    list.push(1); list.push(2); list.push(3);
+
+    assert_eq!(list.peek(), Some(&3));
+    assert_eq!(list.peek_mut(), Some(&mut 3));
+
+    list.peek_mut().map(|value| {
+        *value = 42
+    });
+
+    assert_eq!(list.peek(), Some(&42));
+    assert_eq!(list.pop(), Some(42));
+}
+
+#[test]
+fn into_iter() {
+    let mut list = List::new();
+    list.push(1); list.push(2); list.push(3);
+
+    let mut iter = list.into_iter();
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn iter_mut() {
+    let mut list = List::new();
+    list.push(1); list.push(2); list.push(3);
+
+    let mut iter = list.iter_mut();
+    assert_eq!(iter.next(), Some(&mut 3));
+    assert_eq!(iter.next(), Some(&mut 2));
+    assert_eq!(iter.next(), Some(&mut 1));
+}
+
+
+#[test]
+fn iter() {
+    let mut list = List::new();
+    list.push(1); list.push(2); list.push(3);
 
     let mut iter = list.iter();
     assert_eq!(iter.next(), Some(&3));
@@ -238,3 +1334,1331 @@ fn long_list() {
     drop(list);
 }
 
+#[test]
+fn len() {
+    let mut list = List::new();
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+    assert_eq!(list.pop(), None);
+    assert_eq!(list.len(), 0);
+
+    for i in 0..5000 {
+        list.push(i);
+    }
+    assert_eq!(list.len(), 5000);
+    assert_eq!(list.len(), list.iter().count());
+
+    for elem in list.iter_mut() {
+        *elem += 1;
+    }
+    assert_eq!(list.len(), 5000);
+
+    for _ in 0..3000 {
+        list.pop();
+    }
+    assert_eq!(list.len(), 2000);
+    assert_eq!(list.len(), list.iter().count());
+    assert!(!list.is_empty());
+
+    while list.pop().is_some() {}
+    assert_eq!(list.len(), 0);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn from_iter() {
+    let mut list: List<i32> = (0..5).collect();
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.peek(), Some(&4));
+    for i in (0..5).rev() {
+        assert_eq!(list.pop(), Some(i));
+    }
+    assert_eq!(list.pop(), None);
+
+    let mut list: List<String> = vec!["a".to_string(), "b".to_string()].into_iter().collect();
+    assert_eq!(list.pop(), Some("b".to_string()));
+    assert_eq!(list.pop(), Some("a".to_string()));
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn extend() {
+    let mut list = List::new();
+    list.push(1); list.push(2);
+
+    list.extend(vec![3, 4]);
+    assert_eq!(list.len(), 4);
+
+    list.extend([5, 6].iter());
+    assert_eq!(list.len(), 6);
+
+    for i in (1..=6).rev() {
+        assert_eq!(list.pop(), Some(i));
+    }
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn clone() {
+    let mut list = List::new();
+    for i in 0..100000 {
+        list.push(i);
+    }
+    let mut cloned = list.clone();
+    assert_eq!(cloned.len(), list.len());
+
+    while let Some(elem) = list.pop() {
+        assert_eq!(cloned.pop(), Some(elem));
+    }
+    assert_eq!(cloned.pop(), None);
+}
+
+#[test]
+fn debug() {
+    let mut list = List::new();
+    assert_eq!(format!("{:?}", list), "[]");
+
+    list.push(1); list.push(2); list.push(3);
+    assert_eq!(format!("{:?}", list), "[3, 2, 1]");
+    assert_eq!(list.len(), 3);
+}
+
+#[test]
+fn eq() {
+    let a: List<i32> = (0..3).collect();
+    let b: List<i32> = (0..3).collect();
+    assert_eq!(a, b);
+    assert_eq!(List::<i32>::new(), List::new());
+
+    // prefix iterates [0, 1], longer iterates [0, 1, 2]
+    let prefix: List<i32> = vec![1, 0].into_iter().collect();
+    let longer: List<i32> = vec![2, 1, 0].into_iter().collect();
+    assert_ne!(longer, prefix);
+    assert_ne!(prefix, longer);
+
+    let mismatch: List<i32> = vec![0, 5, 2].into_iter().collect();
+    assert_ne!(a, mismatch);
+}
+
+#[test]
+fn for_loops() {
+    let mut list: List<i32> = (1..=3).collect();
+
+    let mut seen = Vec::new();
+    for elem in &list {
+        seen.push(*elem);
+    }
+    assert_eq!(seen, vec![3, 2, 1]);
+
+    for elem in &mut list {
+        *elem *= 10;
+    }
+
+    let mut owned = Vec::new();
+    for elem in list {
+        owned.push(elem);
+    }
+    assert_eq!(owned, vec![30, 20, 10]);
+}
+
+#[test]
+fn get() {
+    let mut list: List<i32> = (0..5).collect();
+    assert_eq!(list.get(0), Some(&4));
+    assert_eq!(list.get(2), Some(&2));
+    assert_eq!(list.get(4), Some(&0));
+    assert_eq!(list.get(5), None);
+
+    if let Some(elem) = list.get_mut(2) {
+        *elem = 42;
+    }
+    assert_eq!(list.get(2), Some(&42));
+    assert_eq!(list.get_mut(0), Some(&mut 4));
+    assert_eq!(list.get_mut(4), Some(&mut 0));
+    assert_eq!(list.get_mut(5), None);
+}
+
+#[test]
+fn reverse() {
+    let mut list = List::<i32>::new();
+    list.reverse();
+    assert_eq!(list.pop(), None);
+
+    list.push(1);
+    list.reverse();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.pop(), Some(1));
+
+    list.push(1); list.push(2); list.push(3);
+    list.reverse();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop(), Some(2));
+    assert_eq!(list.pop(), Some(3));
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn append() {
+    let mut a: List<i32> = vec![2, 1].into_iter().collect();
+    let mut b: List<i32> = vec![4, 3].into_iter().collect();
+    a.append(&mut b);
+    assert_eq!(a.len(), 4);
+    assert!(b.is_empty());
+    assert_eq!(b.pop(), None);
+    assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+    // b is still usable after being drained
+    b.push(5);
+    assert_eq!(b.len(), 1);
+
+    let mut empty = List::new();
+    empty.append(&mut b);
+    assert_eq!(empty.len(), 1);
+    assert_eq!(empty.pop(), Some(5));
+
+    a.append(&mut List::new());
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn split_off() {
+    let mut list: List<i32> = (0..5).collect();
+    let mut rest = list.split_off(2);
+    assert_eq!(list.len(), 2);
+    assert_eq!(rest.len(), 3);
+    assert_eq!(list.pop(), Some(4));
+    assert_eq!(list.pop(), Some(3));
+    assert_eq!(list.pop(), None);
+    assert_eq!(rest.pop(), Some(2));
+    assert_eq!(rest.pop(), Some(1));
+    assert_eq!(rest.pop(), Some(0));
+    assert_eq!(rest.pop(), None);
+
+    let mut list: List<i32> = (0..3).collect();
+    let rest = list.split_off(0);
+    assert!(list.is_empty());
+    assert_eq!(list.pop(), None);
+    assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+
+    let mut list: List<i32> = (0..3).collect();
+    assert!(list.split_off(3).is_empty());
+    assert!(list.split_off(10).is_empty());
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+}
+
+#[test]
+fn iter_rev() {
+    let list: List<i32> = (0..5).collect();
+    let mut forward: Vec<_> = list.iter().collect();
+    forward.reverse();
+    assert_eq!(list.iter().rev().collect::<Vec<_>>(), forward);
+
+    // mixing both ends never yields an element twice
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&4));
+    assert_eq!(iter.next_back(), Some(&0));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next_back(), Some(&1));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn exact_size() {
+    let mut list: List<i32> = (0..3).collect();
+
+    let mut iter = list.iter();
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    iter.next();
+    assert_eq!(iter.len(), 2);
+    iter.next_back();
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    iter.next();
+    iter.next();
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    let mut iter_mut = list.iter_mut();
+    assert_eq!(iter_mut.size_hint(), (3, Some(3)));
+    iter_mut.next();
+    assert_eq!(iter_mut.len(), 2);
+    iter_mut.next();
+    iter_mut.next();
+    iter_mut.next();
+    assert_eq!(iter_mut.size_hint(), (0, Some(0)));
+
+    let mut into_iter = list.into_iter();
+    assert_eq!(into_iter.size_hint(), (3, Some(3)));
+    into_iter.next();
+    assert_eq!(into_iter.len(), 2);
+    into_iter.next();
+    into_iter.next();
+    into_iter.next();
+    assert_eq!(into_iter.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn clear() {
+    let mut list = List::new();
+    for i in 0..100000 {
+        list.push(i);
+    }
+    list.clear();
+    assert!(list.is_empty());
+    assert_eq!(list.pop(), None);
+
+    list.push(1);
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.pop(), Some(1));
+}
+
+#[test]
+fn contains() {
+    let list: List<i32> = (0..3).collect();
+    assert!(list.contains(&1));
+    assert!(!list.contains(&3));
+    assert!(!List::new().contains(&0));
+}
+
+#[test]
+fn retain() {
+    let mut list: List<i32> = (0..10).collect();
+    list.retain(|elem| elem % 2 == 0);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![8, 6, 4, 2, 0]);
+
+    list.retain(|_| true);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![8, 6, 4, 2, 0]);
+
+    list.retain(|_| false);
+    assert!(list.is_empty());
+    assert_eq!(list.pop(), None);
+
+    // removed elements are dropped exactly once
+    let tracker = DropTracker::new();
+    let mut list = tracker.list(4);
+    let mut keep = false;
+    list.retain(|_| { keep = !keep; keep });
+    assert_eq!(list.len(), 2);
+    assert_eq!(tracker.alive(), 2);
+    drop(list);
+    assert_eq!(tracker.alive(), 0);
+}
+
+#[test]
+fn vec_conversions() {
+    let v = vec![1, 2, 3];
+    let mut list = List::from(v.clone());
+    assert_eq!(list.peek(), Some(&1));
+    assert_eq!(Vec::from(list.clone()), v);
+    assert_eq!(list.clone().into_vec(), v);
+
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop(), Some(2));
+    assert_eq!(list.pop(), Some(3));
+
+    assert_eq!(Vec::from(List::from(Vec::<i32>::new())), Vec::<i32>::new());
+}
+
+#[test]
+fn default() {
+    let mut list: List<i32> = List::default();
+    assert!(list.is_empty());
+    assert_eq!(list, List::new());
+    assert_eq!(list.pop(), None);
+
+    list.push(1);
+    assert_eq!(list.pop(), Some(1));
+}
+
+#[test]
+fn hash() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    assert!(set.insert(List::from(vec![1, 2])));
+    assert!(!set.insert(List::from(vec![1, 2])));
+    assert!(set.insert(List::from(vec![2, 1])));
+    assert!(set.insert(List::from(vec![1])));
+    assert!(set.insert(List::new()));
+    assert_eq!(set.len(), 4);
+    assert!(set.contains(&List::from(vec![1, 2])));
+}
+
+#[test]
+fn peek_nth() {
+    let list: List<i32> = (0..5).collect();
+    assert_eq!(list.peek_nth(0), list.peek());
+    assert_eq!(list.peek_nth(0), Some(&4));
+    assert_eq!(list.peek_nth(2), Some(&2));
+    assert_eq!(list.peek_nth(5), None);
+    assert_eq!(List::<i32>::new().peek_nth(0), None);
+}
+
+#[test]
+fn pop_if() {
+    let mut list: List<i32> = (0..3).collect();
+    assert_eq!(list.pop_if(|elem| *elem == 2), Some(2));
+    assert_eq!(list.len(), 2);
+
+    assert_eq!(list.pop_if(|elem| *elem == 2), None);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.peek(), Some(&1));
+
+    let mut empty = List::<i32>::new();
+    assert_eq!(empty.pop_if(|_| true), None);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn extract_if() {
+    let mut list: List<i32> = (0..10).collect();
+    let evens: Vec<i32> = list.extract_if(|elem| *elem % 2 == 0).collect();
+    assert_eq!(evens, vec![8, 6, 4, 2, 0]);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![9, 7, 5, 3, 1]);
+
+    // stopping early leaves the unvisited nodes in place
+    assert_eq!(list.extract_if(|_| true).next(), Some(9));
+    assert_eq!(list.len(), 4);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![7, 5, 3, 1]);
+}
+
+#[test]
+fn map_into() {
+    let list = List::from(vec![1, 2, 3]);
+    let mut mapped: List<String> = list.map_into(|elem| elem.to_string());
+    assert_eq!(mapped.len(), 3);
+    assert_eq!(mapped.pop(), Some("1".to_string()));
+    assert_eq!(mapped.pop(), Some("2".to_string()));
+    assert_eq!(mapped.pop(), Some("3".to_string()));
+    assert_eq!(mapped.pop(), None);
+}
+
+#[test]
+fn list_macro() {
+    let mut manual = List::new();
+    manual.push(3); manual.push(2); manual.push(1);
+    assert_eq!(list![1, 2, 3], manual);
+    assert_eq!(list![1, 2, 3,], manual);
+
+    let mut list = list![1, 2, 3];
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop(), Some(2));
+    assert_eq!(list.pop(), Some(3));
+
+    let empty: List<i32> = list![];
+    assert!(empty.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let list = List::from(vec![1, 2, 3]);
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, "[1,2,3]");
+
+    let back: List<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, list);
+    assert_eq!(back.peek(), Some(&1));
+}
+
+#[test]
+fn to_vec() {
+    let mut list = List::from(vec![1, 2, 3]);
+    let vec = list.to_vec();
+    assert_eq!(vec, vec![1, 2, 3]);
+    assert_eq!(vec.capacity(), list.len());
+    assert_eq!(list.len(), 3);
+    for elem in vec {
+        assert_eq!(list.pop(), Some(elem));
+    }
+
+    assert_eq!(List::<i32>::new().to_vec(), Vec::<i32>::new());
+}
+
+#[test]
+fn nth_from_tail() {
+    let list: List<i32> = (0..5).collect();
+    assert_eq!(list.nth_from_tail(0), Some(&0));
+    assert_eq!(list.nth_from_tail(2), Some(&2));
+    assert_eq!(list.nth_from_tail(4), Some(&4));
+    assert_eq!(list.nth_from_tail(5), None);
+    assert_eq!(list.nth_from_tail(usize::MAX), None);
+    assert_eq!(List::<i32>::new().nth_from_tail(0), None);
+}
+
+#[test]
+fn fused() {
+    fn assert_fused<I: FusedIterator>(_: &I) {}
+
+    let mut list: List<i32> = (0..2).collect();
+
+    let mut iter = list.iter();
+    assert_fused(&iter);
+    assert_eq!(iter.by_ref().count(), 2);
+    for _ in 0..3 {
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    let mut iter_mut = list.iter_mut();
+    assert_fused(&iter_mut);
+    assert_eq!(iter_mut.by_ref().count(), 2);
+    for _ in 0..3 {
+        assert_eq!(iter_mut.next(), None);
+    }
+
+    let mut into_iter = list.into_iter();
+    assert_fused(&into_iter);
+    assert_eq!(into_iter.by_ref().count(), 2);
+    for _ in 0..3 {
+        assert_eq!(into_iter.next(), None);
+    }
+}
+
+#[test]
+fn insert() {
+    let mut list = List::from(vec![1, 2, 3]);
+    list.insert(0, 0);
+    assert_eq!(list, List::from(vec![0, 1, 2, 3]));
+    list.insert(2, 10);
+    assert_eq!(list, List::from(vec![0, 1, 10, 2, 3]));
+    list.insert(5, 20);
+    assert_eq!(list, List::from(vec![0, 1, 10, 2, 3, 20]));
+    list.insert(100, 30);
+    assert_eq!(list, List::from(vec![0, 1, 10, 2, 3, 20, 30]));
+    assert_eq!(list.len(), 7);
+
+    let mut empty = List::new();
+    empty.insert(3, 1);
+    assert_eq!(empty.len(), 1);
+    assert_eq!(empty.pop(), Some(1));
+}
+
+#[test]
+fn remove() {
+    let mut list = List::from(vec![0, 1, 2, 3, 4]);
+    assert_eq!(list.remove(0), Some(0));
+    assert_eq!(list, List::from(vec![1, 2, 3, 4]));
+    assert_eq!(list.remove(1), Some(2));
+    assert_eq!(list, List::from(vec![1, 3, 4]));
+    assert_eq!(list.remove(2), Some(4));
+    assert_eq!(list, List::from(vec![1, 3]));
+    assert_eq!(list.remove(2), None);
+    assert_eq!(list.remove(10), None);
+    assert_eq!(list.len(), 2);
+
+    assert_eq!(List::<i32>::new().remove(0), None);
+}
+
+#[test]
+fn swap() {
+    let mut list = List::from(vec![0, 1, 2, 3]);
+    list.swap(0, 3);
+    assert_eq!(list, List::from(vec![3, 1, 2, 0]));
+    list.swap(2, 1);
+    assert_eq!(list, List::from(vec![3, 2, 1, 0]));
+    list.swap(1, 1);
+    assert_eq!(list, List::from(vec![3, 2, 1, 0]));
+}
+
+#[test]
+#[should_panic(expected = "swap index out of bounds")]
+fn swap_out_of_bounds() {
+    let mut list = List::from(vec![0, 1, 2]);
+    list.swap(0, 3);
+}
+
+#[test]
+fn merge_sorted() {
+    let mut list = List::from(vec![1, 3, 5, 7]);
+    list.merge_sorted(List::from(vec![2, 4, 6]));
+    assert_eq!(list.len(), 7);
+    assert_eq!(list, List::from(vec![1, 2, 3, 4, 5, 6, 7]));
+
+    list.merge_sorted(List::new());
+    assert_eq!(list, List::from(vec![1, 2, 3, 4, 5, 6, 7]));
+
+    let mut empty = List::new();
+    empty.merge_sorted(List::from(vec![1, 2]));
+    assert_eq!(empty.len(), 2);
+    assert_eq!(empty, List::from(vec![1, 2]));
+}
+
+#[test]
+fn merge_sorted_stable() {
+    use std::cmp::Ordering;
+
+    // Ordered by key only, so the tag tells us where each element came from
+    #[derive(Debug, PartialEq, Eq)]
+    struct Item(i32, char);
+    impl PartialOrd for Item {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Item {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut list = List::from(vec![Item(1, 'a'), Item(2, 'a')]);
+    list.merge_sorted(List::from(vec![Item(1, 'b'), Item(2, 'b')]));
+    assert_eq!(
+        list.into_vec(),
+        vec![Item(1, 'a'), Item(1, 'b'), Item(2, 'a'), Item(2, 'b')]
+    );
+}
+
+#[test]
+fn sort() {
+    let mut list = List::from(vec![5, 1, 4, 9, 2, 8, 3, 7, 6, 0]);
+    list.sort();
+    assert_eq!(list.len(), 10);
+    assert_eq!(list.into_vec(), (0..10).collect::<Vec<_>>());
+
+    // Pseudo-random input with an odd length so the last run is short
+    let mut seed = 42u32;
+    let random: Vec<u32> = (0..1001)
+        .map(|_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            seed % 1000
+        })
+        .collect();
+    let mut list = List::from(random.clone());
+    list.sort();
+    let mut expected = random;
+    expected.sort();
+    assert_eq!(list.into_vec(), expected);
+
+    let mut sorted: List<i32> = List::from((0..100).collect::<Vec<_>>());
+    sorted.sort();
+    assert_eq!(sorted.into_vec(), (0..100).collect::<Vec<_>>());
+
+    let mut reversed: List<i32> = (0..100).collect();
+    reversed.sort();
+    assert_eq!(reversed.into_vec(), (0..100).collect::<Vec<_>>());
+
+    let mut dups = List::from(vec![3, 1, 3, 2, 1, 3]);
+    dups.sort();
+    assert_eq!(dups.into_vec(), vec![1, 1, 2, 3, 3, 3]);
+
+    let mut empty = List::<i32>::new();
+    empty.sort();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn position_find() {
+    let list = List::from(vec![1, 2, 3, 4]);
+    assert_eq!(list.position(|elem| *elem == 1), Some(0));
+    assert_eq!(list.find(|elem| *elem == 1), Some(&1));
+    assert_eq!(list.position(|elem| *elem > 3), Some(3));
+    assert_eq!(list.find(|elem| *elem > 3), Some(&4));
+    assert_eq!(list.position(|elem| *elem > 4), None);
+    assert_eq!(list.find(|elem| *elem > 4), None);
+
+    // Short-circuits on the first match
+    let mut visited = 0;
+    assert_eq!(list.position(|elem| { visited += 1; *elem == 2 }), Some(1));
+    assert_eq!(visited, 2);
+}
+
+#[test]
+fn display() {
+    assert_eq!(List::from(vec![1, 2, 3]).to_string(), "1 -> 2 -> 3");
+    assert_eq!(List::from(vec![1]).to_string(), "1");
+    assert_eq!(List::<i32>::new().to_string(), "(empty)");
+}
+
+#[test]
+fn truncate() {
+    let tracker = DropTracker::new();
+    let mut list = tracker.list(5);
+
+    list.truncate(10);
+    assert_eq!(list.len(), 5);
+    list.truncate(2);
+    assert_eq!(list.len(), 2);
+    // Dropped elements are freed right away
+    assert_eq!(tracker.alive(), 2);
+    list.truncate(0);
+    assert!(list.is_empty());
+    assert_eq!(tracker.alive(), 0);
+
+    let mut list = List::from(vec![1, 2, 3]);
+    list.truncate(2);
+    assert_eq!(list, List::from(vec![1, 2]));
+}
+
+#[test]
+fn dedup() {
+    let mut list = List::from(vec![1, 1, 2, 3, 3, 3, 1]);
+    list.dedup();
+    assert_eq!(list.len(), 4);
+    assert_eq!(list, List::from(vec![1, 2, 3, 1]));
+
+    let mut same = List::from(vec![7, 7, 7, 7]);
+    same.dedup();
+    assert_eq!(same.len(), 1);
+    assert_eq!(same, List::from(vec![7]));
+
+    let mut unique = List::from(vec![1, 2, 3]);
+    unique.dedup();
+    assert_eq!(unique.len(), 3);
+    assert_eq!(unique, List::from(vec![1, 2, 3]));
+
+    let mut empty = List::<i32>::new();
+    empty.dedup();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn node_recycling() {
+    fn free_len<T>(list: &List<T>) -> usize {
+        let mut count = 0;
+        let mut link = list.free.as_deref();
+        while let Some(node) = link {
+            count += 1;
+            link = node.next.as_deref();
+        }
+        count
+    }
+    fn head_ptr<T>(list: &List<T>) -> *const Node<T> {
+        list.head.as_deref().unwrap()
+    }
+
+    // Off by default: popped nodes are freed straight away
+    let mut list = List::new();
+    list.push("a".to_string());
+    list.pop();
+    assert_eq!(free_len(&list), 0);
+
+    let mut list = List::with_recycling();
+    list.push("a".to_string());
+    let first = head_ptr(&list);
+
+    // The popped node is parked and handed straight back to the next push
+    for i in 0..1000 {
+        assert!(list.pop().is_some());
+        assert_eq!(free_len(&list), 1);
+        list.push(i.to_string());
+        assert_eq!(head_ptr(&list), first);
+        assert_eq!(free_len(&list), 0);
+    }
+    assert_eq!(list.peek(), Some(&"999".to_string()));
+
+    for i in 0..10 {
+        list.push(i.to_string());
+    }
+    while list.pop().is_some() {}
+    assert_eq!(free_len(&list), 11);
+    assert!(list.is_empty());
+
+    list.shrink_to_fit();
+    assert_eq!(free_len(&list), 0);
+    list.push("b".to_string());
+    assert_eq!(list.pop(), Some("b".to_string()));
+
+    // Parked nodes hold no element, so dropping them doesn't drop anything twice
+    let tracker = DropTracker::new();
+    let mut list = List::with_recycling();
+    list.extend((0..100000).map(|_| tracker.elem()));
+    for _ in 0..50000 {
+        list.pop();
+    }
+    assert_eq!(tracker.alive(), 50000);
+    assert_eq!(free_len(&list), 50000);
+    drop(list);
+    assert_eq!(tracker.alive(), 0);
+    // insert and the cursor take parked nodes too, and the setting is carried over
+    let mut list = List::with_recycling();
+    list.extend([1, 2, 3]);
+    list.pop();
+    list.pop();
+    list.insert(1, 4);
+    assert_eq!(free_len(&list), 1);
+    list.cursor_front_mut().insert_after(5);
+    assert_eq!(free_len(&list), 0);
+    assert_eq!(list, vec![1, 5, 4]);
+
+    let mut cloned = list.clone();
+    cloned.pop();
+    assert_eq!(free_len(&cloned), 1);
+    let mut rest = list.split_off(1);
+    rest.pop();
+    assert_eq!(free_len(&rest), 1);
+}
+
+#[test]
+fn cursor_mut() {
+    let mut list = List::from(vec![1, 2, 3, 4]);
+
+    let mut cursor = list.cursor_front_mut();
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 2));
+    cursor.insert_after(10);
+    assert_eq!(cursor.current(), Some(&mut 2));
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 10));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert_eq!(cursor.current(), Some(&mut 4));
+    if let Some(elem) = cursor.current() {
+        *elem = 40;
+    }
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.move_next();
+    cursor.insert_after(5);
+    assert_eq!(cursor.current(), Some(&mut 5));
+
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.into_vec(), vec![1, 2, 10, 40, 5]);
+
+    let mut empty = List::new();
+    let mut cursor = empty.cursor_front_mut();
+    assert_eq!(cursor.current(), None);
+    cursor.insert_after(1);
+    assert_eq!(cursor.remove_current(), Some(1));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn index() {
+    let mut list = List::from(vec![1, 2, 3]);
+    assert_eq!(list[0], 1);
+    assert_eq!(list[2], 3);
+
+    list[1] = 20;
+    list[2] += 10;
+    assert_eq!(list, List::from(vec![1, 20, 13]));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn index_out_of_bounds() {
+    let list = List::from(vec![1, 2, 3]);
+    let _ = list[3];
+}
+
+#[test]
+fn iter_from() {
+    let list = List::from(vec![0, 1, 2, 3]);
+    assert!(list.iter_from(0).eq(list.iter()));
+
+    let mut iter = list.iter_from(2);
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+
+    let mut past_end = list.iter_from(10);
+    assert_eq!(past_end.len(), 0);
+    assert_eq!(past_end.next(), None);
+    // Huge offsets stop at the end of the list instead of counting all the way up
+    let mut far = list.iter_from(usize::MAX);
+    assert_eq!(far.len(), 0);
+    assert_eq!(far.next(), None);
+}
+
+#[test]
+fn last() {
+    let mut list = List::new();
+    assert_eq!(list.last(), None);
+    assert_eq!(list.last_mut(), None);
+
+    list.push(1);
+    assert_eq!(list.last(), list.peek());
+    assert_eq!(list.last(), Some(&1));
+
+    list.push(2); list.push(3);
+    assert_eq!(list.last(), Some(&1));
+    if let Some(elem) = list.last_mut() {
+        *elem = 10;
+    }
+    assert_eq!(list.last(), Some(&10));
+    assert_eq!(list.peek(), Some(&3));
+}
+
+#[test]
+fn rotate_left() {
+    let mut list = List::from(vec![0, 1, 2, 3, 4]);
+    list.rotate_left(0);
+    assert_eq!(list, List::from(vec![0, 1, 2, 3, 4]));
+    list.rotate_left(2);
+    assert_eq!(list, List::from(vec![2, 3, 4, 0, 1]));
+    list.rotate_left(5);
+    assert_eq!(list, List::from(vec![2, 3, 4, 0, 1]));
+    list.rotate_left(7);
+    assert_eq!(list, List::from(vec![4, 0, 1, 2, 3]));
+    assert_eq!(list.len(), 5);
+
+    let mut empty = List::<i32>::new();
+    empty.rotate_left(3);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn sum_product() {
+    let list = List::from(vec![1, 2, 3, 4]);
+    assert_eq!(list.sum::<i32>(), 10);
+    assert_eq!(list.product::<i32>(), 24);
+    assert_eq!(list.iter().copied().sum::<i32>(), 10);
+
+    let empty = List::<i32>::new();
+    assert_eq!(empty.sum::<i32>(), 0);
+    assert_eq!(empty.product::<i32>(), 1);
+}
+
+#[test]
+fn min_max() {
+    let empty = List::<i32>::new();
+    assert_eq!(empty.min(), None);
+    assert_eq!(empty.max(), None);
+
+    let single = List::from(vec![7]);
+    assert_eq!(single.min(), Some(&7));
+    assert_eq!(single.max(), Some(&7));
+
+    let list = List::from(vec![3, 1, 4, 1, 5, 9, 2, 9]);
+    assert_eq!(list.min(), Some(&1));
+    assert_eq!(list.max(), Some(&9));
+
+    // Ties resolve to the first occurrence
+    assert!(std::ptr::eq(list.min().unwrap(), list.get(1).unwrap()));
+    assert!(std::ptr::eq(list.max().unwrap(), list.get(5).unwrap()));
+}
+
+#[test]
+fn partition() {
+    let list = List::from(vec![1, 2, 3, 4, 5, 6]);
+    let (evens, odds) = list.partition(|elem| elem % 2 == 0);
+    assert_eq!(evens.len(), 3);
+    assert_eq!(odds.len(), 3);
+    assert_eq!(evens.into_vec(), vec![2, 4, 6]);
+    assert_eq!(odds.into_vec(), vec![1, 3, 5]);
+
+    let (all, none) = List::from(vec![1, 2]).partition(|_| true);
+    assert_eq!(all, List::from(vec![1, 2]));
+    assert!(none.is_empty());
+}
+
+#[test]
+fn fold_reduce() {
+    let list = List::from(vec![3, 1, 4, 1, 5]);
+    assert_eq!(list.fold(0, |acc, elem| acc + elem), 14);
+    assert_eq!(list.fold(String::new(), |acc, elem| acc + &elem.to_string()), "31415");
+    assert_eq!(list.reduce(|a, b| *a.max(b)), Some(5));
+
+    let empty = List::<i32>::new();
+    assert_eq!(empty.fold(7, |acc, elem| acc + elem), 7);
+    assert_eq!(empty.reduce(|a, b| a + b), None);
+}
+
+#[test]
+fn try_from_slice() {
+    let mut list = List::try_from(&[1, 2, 3][..]).unwrap();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop(), Some(2));
+    assert_eq!(list.pop(), Some(3));
+
+    let too_long = vec![0u8; MAX_FROM_SLICE + 1];
+    assert_eq!(
+        List::try_from(&too_long[..]).err(),
+        Some(SliceTooLong(MAX_FROM_SLICE + 1))
+    );
+    assert_eq!(
+        SliceTooLong(MAX_FROM_SLICE + 1).to_string(),
+        "slice of length 1000001 exceeds the limit of 1000000"
+    );
+    // Usable with ? in functions returning Box<dyn Error>
+    #[cfg(feature = "std")]
+    {
+        fn build(slice: &[u8]) -> Result<List<u8>, Box<dyn std::error::Error>> {
+            Ok(List::try_from(slice)?)
+        }
+        assert!(build(&too_long).is_err());
+        assert_eq!(build(&[1, 2]).unwrap().len(), 2);
+    }
+}
+
+#[test]
+fn pairs() {
+    assert_eq!(List::<i32>::new().pairs().count(), 0);
+    assert_eq!(List::from(vec![1]).pairs().count(), 0);
+
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(list.pairs().collect::<Vec<_>>(), vec![(&1, &2), (&2, &3)]);
+}
+
+#[test]
+fn chunks() {
+    let list = List::from(vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        list.chunks(2).collect::<Vec<_>>(),
+        vec![vec![&1, &2], vec![&3, &4], vec![&5]]
+    );
+    assert_eq!(list.chunks(10).collect::<Vec<_>>(), vec![vec![&1, &2, &3, &4, &5]]);
+    assert_eq!(List::<i32>::new().chunks(3).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn chunks_zero() {
+    let list = List::from(vec![1, 2, 3]);
+    let _ = list.chunks(0);
+}
+
+#[test]
+fn running_fold() {
+    let list = List::from(vec![1, 2, 3]);
+    let sums = list.running_fold(0, |acc, elem| acc + elem);
+    assert_eq!(sums.len(), 3);
+    assert_eq!(sums, List::from(vec![1, 3, 6]));
+
+    assert!(List::<i32>::new().running_fold(0, |acc, elem| acc + elem).is_empty());
+}
+
+#[test]
+fn zip() {
+    let zipped = List::from(vec![1, 2, 3]).zip(List::from(vec!['a', 'b', 'c']));
+    assert_eq!(zipped.len(), 3);
+    assert_eq!(zipped.into_vec(), vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+
+    let tracker = DropTracker::new();
+    let zipped = List::from(vec![1, 2]).zip(tracker.list(4));
+    assert_eq!(zipped.len(), 2);
+    // The two unpaired elements of the longer list are already gone
+    assert_eq!(tracker.alive(), 2);
+    drop(zipped);
+    assert_eq!(tracker.alive(), 0);
+
+    let zipped = tracker.list(4).zip(List::from(vec![1]));
+    assert_eq!(zipped.len(), 1);
+    assert_eq!(tracker.alive(), 1);
+    assert_eq!(zipped.into_vec()[0].1, 1);
+    assert_eq!(tracker.alive(), 0);
+}
+
+#[test]
+fn flatten() {
+    let nested = List::from(vec![List::from(vec![1, 2]), List::new(), List::from(vec![3])]);
+    let flat = nested.flatten();
+    assert_eq!(flat.len(), 3);
+    assert_eq!(flat.into_vec(), vec![1, 2, 3]);
+
+    assert!(List::<List<i32>>::new().flatten().is_empty());
+}
+
+#[test]
+fn clone_from() {
+    fn node_ptrs<T>(list: &List<T>) -> Vec<*const Node<T>> {
+        let mut ptrs = Vec::new();
+        let mut link = list.head.as_deref();
+        while let Some(node) = link {
+            ptrs.push(node as *const Node<T>);
+            link = node.next.as_deref();
+        }
+        ptrs
+    }
+
+    // Longer destination: every surviving node is reused in place
+    let source = List::from(vec![1, 2, 3]);
+    let mut dest = List::from(vec![9, 9, 9, 9, 9]);
+    let before = node_ptrs(&dest);
+    dest.clone_from(&source);
+    assert_eq!(dest, source);
+    assert_eq!(dest.len(), 3);
+    assert_eq!(node_ptrs(&dest), before[..3]);
+
+    // Shorter destination: existing nodes are kept, only the extra ones are new
+    let source = List::from(vec![1, 2, 3, 4]);
+    let mut dest = List::from(vec![7, 8]);
+    let before = node_ptrs(&dest);
+    dest.clone_from(&source);
+    assert_eq!(dest, source);
+    assert_eq!(dest.len(), 4);
+    assert_eq!(node_ptrs(&dest)[..2], before[..]);
+
+    let mut dest = List::from(vec![1]);
+    dest.clone_from(&List::new());
+    assert!(dest.is_empty());
+}
+
+#[test]
+fn send_sync() {
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    assert_send::<List<i32>>();
+    assert_sync::<List<i32>>();
+    assert_send::<IntoIter<String>>();
+    assert_sync::<IntoIter<String>>();
+    assert_send::<Iter<'static, i32>>();
+    assert_sync::<Iter<'static, i32>>();
+    assert_send::<IterMut<'static, i32>>();
+    assert_sync::<IterMut<'static, i32>>();
+
+    // A list really can be moved to another thread and back
+    let list = List::from(vec![1, 2, 3]);
+    let list = std::thread::spawn(move || list).join().unwrap();
+    assert_eq!(list.into_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn covariance() {
+    // These only compile if List, Iter and IntoIter are covariant in their parameters
+    fn shorten_list<'a>(list: List<&'static str>) -> List<&'a str> {
+        list
+    }
+    fn shorten_iter<'a, 'b: 'a>(iter: Iter<'b, &'static str>) -> Iter<'a, &'a str> {
+        iter
+    }
+    fn shorten_into_iter<'a>(iter: IntoIter<&'static str>) -> IntoIter<&'a str> {
+        iter
+    }
+
+    let local = String::from("local");
+    let mut list = shorten_list(List::from(vec!["static"]));
+    list.push(&local);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!["local", "static"]);
+
+    let statics = List::from(vec!["a", "b"]);
+    assert_eq!(shorten_iter(statics.iter()).copied().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(shorten_into_iter(statics.into_iter()).count(), 2);
+}
+
+#[test]
+fn push_slice() {
+    let mut list = List::new();
+    list.push(0);
+    list.push_slice(&[1, 2, 3]);
+    assert_eq!(list.len(), 4);
+    assert_eq!(list.into_vec(), vec![3, 2, 1, 0]);
+
+    // Parked nodes from earlier pops get reused
+    let mut list = List::with_recycling();
+    list.extend(["x".to_string(), "y".to_string()]);
+    list.pop();
+    list.pop();
+    list.push_slice(&["a".to_string(), "b".to_string()]);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.pop(), Some("b".to_string()));
+    assert_eq!(list.pop(), Some("a".to_string()));
+
+    let mut list = List::from(vec![1]);
+    list.push_slice(&[]);
+    assert_eq!(list.len(), 1);
+}
+
+#[test]
+fn try_fold() {
+    let list = List::from(vec![1, 2, 3]);
+    assert_eq!(list.try_fold(0, |acc, elem| Ok::<_, ()>(acc + elem)), Ok(6));
+    assert_eq!(List::<i32>::new().try_fold(5, |acc, _| Err(acc)), Ok::<_, i32>(5));
+
+    // Stops at the first failing element without visiting the rest
+    let list = List::from(vec![1, -2, 3, -4]);
+    let mut visited = 0;
+    let result = list.try_fold(0, |acc, &elem| {
+        visited += 1;
+        if elem < 0 { Err(elem) } else { Ok(acc + elem) }
+    });
+    assert_eq!(result, Err(-2));
+    assert_eq!(visited, 2);
+}
+
+#[test]
+fn all_any() {
+    let empty = List::<i32>::new();
+    assert!(empty.all(|_| false));
+    assert!(!empty.any(|_| true));
+
+    let list = List::from(vec![2, 4, 5, 6]);
+    assert!(list.all(|elem| *elem > 0));
+    assert!(!list.all(|elem| elem % 2 == 0));
+    assert!(list.any(|elem| elem % 2 != 0));
+    assert!(!list.any(|elem| *elem > 6));
+
+    // Short-circuits on the first deciding element
+    let mut visited = 0;
+    assert!(list.any(|elem| {
+        visited += 1;
+        *elem == 4
+    }));
+    assert_eq!(visited, 2);
+}
+
+#[test]
+fn take_drop_while() {
+    let list = List::from(vec![1, 3, 4, 5, 7]);
+    let is_odd = |elem: &i32| elem % 2 != 0;
+
+    // Matches a prefix
+    let taken = list.clone().take_while(is_odd);
+    let dropped = list.clone().drop_while(is_odd);
+    assert_eq!(taken.len(), 2);
+    assert_eq!(taken.into_vec(), vec![1, 3]);
+    assert_eq!(dropped.len(), 3);
+    assert_eq!(dropped.into_vec(), vec![4, 5, 7]);
+
+    // Matches everything
+    assert_eq!(list.clone().take_while(|_| true), list);
+    assert!(list.clone().drop_while(|_| true).is_empty());
+
+    // Matches nothing
+    assert!(list.clone().take_while(|_| false).is_empty());
+    assert_eq!(list.clone().drop_while(|_| false), list);
+
+    // Nodes are moved, not reallocated
+    let head = list.head.as_deref().unwrap() as *const Node<i32>;
+    let taken = list.take_while(is_odd);
+    assert_eq!(taken.head.as_deref().unwrap() as *const Node<i32>, head);
+}
+
+#[test]
+fn eq_slice_vec() {
+    let mut list = List::new();
+    list.push(1); list.push(2); list.push(3);
+
+    // Compared in pop order: the head lines up with index 0
+    assert_eq!(list, vec![3, 2, 1]);
+    assert_eq!(list, [3, 2, 1][..]);
+    assert_ne!(list, vec![1, 2, 3]);
+    assert_ne!(list, vec![3, 2]);
+    assert_ne!(list, [3, 2, 1, 0][..]);
+    assert_eq!(List::<i32>::new(), Vec::new());
+    assert_eq!(List::from(vec![4, 5]), vec![4, 5]);
+}
+
+#[test]
+fn peek_mut_guard() {
+    let mut list = List::<String>::new();
+    assert!(list.peek_mut_guard().is_none());
+
+    list.push("a".to_string());
+    list.push("b".to_string());
+    {
+        let mut guard = list.peek_mut_guard().unwrap();
+        assert_eq!(*guard, "b");
+        guard.push('!');
+        assert_eq!(guard.len(), 2);
+    }
+    assert_eq!(list.peek(), Some(&"b!".to_string()));
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.into_vec(), vec!["b!", "a"]);
+}
+
+#[test]
+fn count_if() {
+    let list = List::from(vec![1, 2, 3, 4, 6]);
+    assert_eq!(list.count_if(|elem| elem % 2 == 0), 3);
+    assert_eq!(list.count_if(|_| true), list.len());
+    assert_eq!(list.count_if(|_| false), 0);
+    assert_eq!(List::<i32>::new().count_if(|_| true), 0);
+}
+
+#[test]
+fn rchunks() {
+    let list = List::from(vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        list.rchunks(2).collect::<Vec<_>>(),
+        vec![vec![&1], vec![&2, &3], vec![&4, &5]]
+    );
+    assert_eq!(
+        list.rchunks(5).collect::<Vec<_>>(),
+        vec![vec![&1, &2, &3, &4, &5]]
+    );
+    assert_eq!(list.rchunks(10).collect::<Vec<_>>(), vec![vec![&1, &2, &3, &4, &5]]);
+    assert_eq!(List::<i32>::new().rchunks(3).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn rchunks_zero() {
+    let list = List::from(vec![1, 2, 3]);
+    let _ = list.rchunks(0);
+}
+
+#[test]
+fn push_slice_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut list = List::new();
+    list.push(PanicOnClone(0));
+    let items = [PanicOnClone(1), PanicOnClone(2), PanicOnClone(-1), PanicOnClone(3)];
+    assert!(catch_unwind(AssertUnwindSafe(|| list.push_slice(&items))).is_err());
+
+    // The two nodes pushed before the panic are counted
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().count(), 3);
+    assert_eq!(list.into_vec(), vec![PanicOnClone(2), PanicOnClone(1), PanicOnClone(0)]);
+}
+
+#[test]
+fn clone_from_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let source = List::from(vec![PanicOnClone(1), PanicOnClone(2), PanicOnClone(-1), PanicOnClone(4)]);
+    let mut dest = List::from(vec![PanicOnClone(0)]);
+    assert!(catch_unwind(AssertUnwindSafe(|| dest.clone_from(&source))).is_err());
+
+    // The overwritten head and the one appended node are all that's there
+    assert_eq!(dest.len(), 2);
+    assert_eq!(dest.iter().count(), 2);
+    assert_eq!(dest.into_vec(), vec![PanicOnClone(1), PanicOnClone(2)]);
+}
+
+#[test]
+fn merge_sorted_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut list = List::from(vec![PanicOnCmp(1), PanicOnCmp(4)]);
+    let other = List::from(vec![PanicOnCmp(2), PanicOnCmp(-1), PanicOnCmp(5)]);
+    assert!(catch_unwind(AssertUnwindSafe(|| list.merge_sorted(other))).is_err());
+
+    // Every node from both lists is relinked into self
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.iter().count(), 5);
+    let mut values: Vec<i32> = list.iter().map(|elem| elem.0).collect();
+    values.sort();
+    assert_eq!(values, vec![-1, 1, 2, 4, 5]);
+}
+
+#[test]
+fn sort_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut list: List<PanicOnCmp> = [5, 3, 8, -1, 2, 7].into_iter().map(PanicOnCmp).collect();
+    assert!(catch_unwind(AssertUnwindSafe(|| list.sort())).is_err());
+
+    // The order is unspecified after a panic, but every element is still linked in
+    assert_eq!(list.len(), 6);
+    assert_eq!(list.iter().count(), 6);
+    let mut values: Vec<i32> = list.iter().map(|elem| elem.0).collect();
+    values.sort();
+    assert_eq!(values, vec![-1, 2, 3, 5, 7, 8]);
+}
+
+#[test]
+fn dedup_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut list = List::from(vec![PanicOnCmp(1), PanicOnCmp(1), PanicOnCmp(2), PanicOnCmp(-1), PanicOnCmp(3)]);
+    assert!(catch_unwind(AssertUnwindSafe(|| list.dedup())).is_err());
+
+    // Duplicates removed before the panic stay removed, nothing else is lost
+    assert_eq!(list.len(), 4);
+    assert_eq!(list.iter().count(), 4);
+    assert_eq!(list.iter().map(|elem| elem.0).collect::<Vec<_>>(), vec![1, 2, -1, 3]);
+}
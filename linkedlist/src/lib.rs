@@ -0,0 +1,16 @@
+//关闭默认的std feature后，good_stack只依赖core和alloc，可以在有分配器的no_std环境使用
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod bad_stack;
+pub mod bounded_list;
+pub mod good_stack;
+#[cfg(feature = "std")]
+pub mod lru_cache;
+#[cfg(feature = "std")]
+pub mod persistent_list_sync;
+#[cfg(feature = "std")]
+pub mod persitent_list;
+pub mod queue;
@@ -0,0 +1,92 @@
+//HashMap负责查找，good_stack记录访问顺序：head是最近用过的key，末尾就是要淘汰的那个
+//单链表挪动key需要O(n)，这里主要是演示链表作为基础组件的用法
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::good_stack::List;
+
+pub struct LruCache<K, V> {
+    map: HashMap<K, V>,
+    order: List<K>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        LruCache { map: HashMap::new(), order: List::new(), capacity }
+    }
+
+    //把key挪到head，表示刚刚用过
+    fn touch(&mut self, k: &K) {
+        if let Some(index) = self.order.position(|key| key == k) {
+            let key = self.order.remove(index).unwrap();
+            self.order.push(key);
+        }
+    }
+
+    pub fn get(&mut self, k: &K) -> Option<&V> {
+        if !self.map.contains_key(k) {
+            return None;
+        }
+        self.touch(k);
+        self.map.get(k)
+    }
+
+    //插入或更新，超出容量时淘汰最久没用过的key
+    pub fn put(&mut self, k: K, v: V) {
+        if self.map.insert(k.clone(), v).is_some() {
+            self.touch(&k);
+            return;
+        }
+        self.order.push(k);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.remove(self.order.len() - 1) {
+                self.map.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LruCache;
+
+    #[test]
+    fn eviction() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.get(&1), Some(&"one"));
+
+        // 2 is the least recently used now
+        cache.put(3, "three");
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.get(&3), Some(&"three"));
+
+        // Updating an existing key refreshes it instead of evicting
+        cache.put(1, "uno");
+        cache.put(4, "four");
+        assert_eq!(cache.get(&3), None);
+        assert_eq!(cache.get(&1), Some(&"uno"));
+        assert_eq!(cache.get(&4), Some(&"four"));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache = LruCache::new(0);
+        cache.put(1, 1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&1), None);
+    }
+}
@@ -1,6 +1,3 @@
-mod bad_stack;
-mod good_stack;
-mod persitent_list;
 fn main() {
     println!("Hello, world!");
 }
@@ -0,0 +1,171 @@
+//persitent_list的线程安全版本：Rc换成Arc，接口和结构共享方式完全一样
+//T: Send + Sync 时List<T>自动是Send + Sync，可以在线程之间共享
+use std::sync::Arc;
+
+pub struct List<T> {
+    head: Link<T>,
+}
+
+type Link<T> = Option<Arc<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List { head: None }
+    }
+
+    pub fn prepend(&self, elem: T) -> List<T> {
+        List { head: Some(Arc::new(Node {
+            elem,
+            //clone Arc只增加原子引用计数，不会拷贝节点
+            next: self.head.clone(),
+        }))}
+    }
+
+    pub fn tail(&self) -> List<T> {
+        List { head: self.head.as_ref().and_then(|node| node.next.clone()) }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem )
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            //只有最后一个持有者才能拿到节点继续往下释放
+            //不能用try_unwrap：两个线程同时失败时，最后一次减计数会递归释放整条尾巴
+            match Arc::into_inner(node) {
+                Some(mut node) => head = node.next.take(),
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+    use std::sync::Barrier;
+    use std::thread;
+
+    #[test]
+    fn basics() {
+        let list = List::new();
+        assert_eq!(list.head(), None);
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.head(), Some(&3));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&2));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&1));
+
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+
+        // Make sure empty tail works
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+    }
+
+    #[test]
+    fn iter() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<List<i32>>();
+    }
+
+    #[test]
+    fn threads() {
+        let base = List::new().prepend(1).prepend(2).prepend(3);
+
+        // Every thread walks the same shared nodes concurrently
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    assert_eq!(base.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+                });
+            }
+        });
+
+        // Lists sharing a tail can also be moved into other threads
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = base.prepend(10 + i);
+                thread::spawn(move || list.iter().sum::<i32>())
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), 16 + i as i32);
+        }
+        assert_eq!(base.head(), Some(&3));
+    }
+
+    #[test]
+    fn concurrent_drop_long_tail() {
+        let mut tail = List::new();
+        for i in 0..100_000 {
+            tail = tail.prepend(i);
+        }
+
+        // Many lists share the same long tail and are all dropped at once;
+        // whichever thread lets go last must free the tail without recursing
+        let lists: Vec<Vec<_>> = (0..8)
+            .map(|t| (0..16).map(|i| tail.prepend(t * 16 + i)).collect())
+            .collect();
+        drop(tail);
+        let barrier = Barrier::new(lists.len());
+        thread::scope(|s| {
+            for group in lists {
+                let barrier = &barrier;
+                s.spawn(move || {
+                    barrier.wait();
+                    drop(group);
+                });
+            }
+        });
+    }
+}
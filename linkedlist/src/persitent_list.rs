@@ -1,4 +1,6 @@
 // in third.rs
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 pub struct List<T> {
@@ -10,6 +12,9 @@ type Link<T> = Option<Rc<Node<T>>>;
 struct Node<T> {
     elem: T,
     next: Link<T>,
+    //以该节点为head的链表长度。节点不可变且可能被多个链表共享，
+    //所以长度缓存在节点上而不是List上，prepend和tail都还是O(1)
+    len: usize,
 }
 
 pub struct Iter<'a, T> {
@@ -21,11 +26,21 @@ impl<T> List<T> {
         List { head: None }
     }
 
+    //v[0]成为head；消耗Vec，倒着prepend，元素直接移进节点，不需要T: Clone
+    pub fn from_vec(v: Vec<T>) -> List<T> {
+        let mut list = List::new();
+        for elem in v.into_iter().rev() {
+            list = list.prepend(elem);
+        }
+        list
+    }
+
     pub fn prepend(&self, elem: T) -> List<T> {
         List { head: Some(Rc::new(Node {
-            elem: elem,
+            elem,
             //option也实现了clone trait，会clone内部元素返回option
             next: self.head.clone(),
+            len: self.len() + 1,
         }))}
     }
 
@@ -43,9 +58,190 @@ impl<T> List<T> {
         self.head.as_ref().map(|node| &node.elem )
     }
 
+    //和slice的split_first一样，同时拿到head和剩下的链表；tail直接共享节点，是O(1)的
+    pub fn split_first(&self) -> Option<(&T, List<T>)> {
+        self.head.as_ref().map(|node| (&node.elem, List { head: node.next.clone() }))
+    }
+
     pub fn iter(&self) -> Iter<'_, T> {
         Iter { next: self.head.as_deref() }
     }
+
+    //每个元素连同它离head的距离一起返回，head是0
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter().enumerate()
+    }
+
+    //下标0是head，越界返回None
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    //和Iterator::nth/count同名，但直接在链表上调用，可以反复用而不用每次重新建迭代器
+    pub fn nth(&self, n: usize) -> Option<&T> {
+        self.get(n)
+    }
+
+    //长度缓存在head节点上，不需要遍历
+    pub fn count(&self) -> usize {
+        self.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.head.as_ref().map_or(0, |node| node.len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    //有多少个链表（包括其他节点的next）共享当前head节点，空链表返回0
+    pub fn head_ref_count(&self) -> usize {
+        self.head.as_ref().map_or(0, Rc::strong_count)
+    }
+
+    //对每个元素调用f生成一条新链表，顺序不变，原链表不受影响；
+    //借助FromIterator先收集再倒着prepend
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> List<U> {
+        self.iter().map(f).collect()
+    }
+
+    //从第n个节点开始的子链表，直接共享这些节点，不拷贝元素；越界返回空链表
+    pub fn skip(&self, n: usize) -> List<T> {
+        //长度缓存在节点上，越界直接返回，不用一步步走完n次
+        if n >= self.len() {
+            return List::new();
+        }
+        let mut link = self.head.as_ref();
+        for _ in 0..n {
+            link = link.and_then(|node| node.next.as_ref());
+        }
+        List { head: link.cloned() }
+    }
+}
+
+impl<T: Clone> List<T> {
+    //前n个元素组成的新链表，第n个节点之后的next不同所以没法共享，只能拷贝；n超过长度时返回整个链表的拷贝
+    pub fn take(&self, n: usize) -> List<T> {
+        self.iter().take(n).cloned().collect()
+    }
+
+    //只保留f返回true的元素，顺序不变；哪怕全部保留也是拷贝到新节点里，不和原链表共享
+    pub fn filter<F: FnMut(&T) -> bool>(&self, mut f: F) -> List<T> {
+        self.iter().filter(|elem| f(elem)).cloned().collect()
+    }
+
+    //从head到尾拷贝到Vec里，长度缓存在节点上，可以一次分配好
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend(self.iter().cloned());
+        vec
+    }
+
+    //消耗链表转换成Vec，顺序从head到尾。只被当前链表持有的节点直接把元素移出来；
+    //一旦碰到还被别的链表共享的节点，它和它后面的节点都不能拆，只能拷贝剩下的元素
+    pub fn into_vec(mut self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len());
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => {
+                    head = node.next.take();
+                    vec.push(node.elem);
+                }
+                Err(shared) => {
+                    let rest = List { head: Some(shared) };
+                    vec.extend(rest.iter().cloned());
+                    break;
+                }
+            }
+        }
+        vec
+    }
+
+    //从head开始依次prepend到新链表上，顺序自然就反过来了；元素要拷贝到新节点里，原链表不受影响
+    pub fn reverse(&self) -> List<T> {
+        let mut reversed = List::new();
+        for elem in self.iter() {
+            reversed = reversed.prepend(elem.clone());
+        }
+        reversed
+    }
+
+    //结果先是self的元素再是other的元素：other的节点直接共享，self的节点需要拷贝
+    //单链表只能从头prepend，所以先把self的元素引用收集起来再倒着prepend到other上
+    pub fn append(&self, other: &List<T>) -> List<T> {
+        let elems: Vec<&T> = self.iter().collect();
+        let mut list = other.clone();
+        for elem in elems.into_iter().rev() {
+            list = list.prepend(elem.clone());
+        }
+        list
+    }
+}
+
+impl<T: PartialEq> List<T> {
+    //沿着共享的节点从head往后找，找到第一个就返回
+    pub fn contains(&self, target: &T) -> bool {
+        self.iter().any(|elem| elem == target)
+    }
+}
+
+//按顺序把几条链表接成一条：最后一条的节点直接共享，前面的都要拷贝；空切片返回空链表
+//从后往前逐个append，每条链表只拷贝一次
+pub fn concat_all<T: Clone>(lists: &[List<T>]) -> List<T> {
+    let mut iter = lists.iter().rev();
+    let mut result = iter.next().cloned().unwrap_or_default();
+    for list in iter {
+        result = list.append(&result);
+    }
+    result
+}
+
+//链表不可变，clone只需要增加head的引用计数，不拷贝任何元素，也不需要T: Clone
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List { head: self.head.clone() }
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+//和PartialEq一致，只看元素不看节点是否共享：先hash长度再按顺序hash每个元素
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for elem in self.iter() {
+            elem.hash(state);
+        }
+    }
+}
+
+//从head到尾输出，形如 [3, 2, 1]
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+//和迭代顺序一致：第一个元素成为head。
+//只能从头prepend，所以先收集到Vec里再交给from_vec倒着prepend
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        List::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
@@ -112,4 +308,325 @@ mod test {
         assert_eq!(iter.next(), Some(&2));
         assert_eq!(iter.next(), Some(&1));
     }
+
+    #[test]
+    fn len() {
+        let list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+
+        let shorter = list.tail();
+        assert_eq!(shorter.len(), 2);
+        let branch = shorter.prepend(4).prepend(5);
+        assert_eq!(branch.len(), 4);
+        assert_eq!(list.len(), 3);
+
+        let empty = list.tail().tail().tail();
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.tail().len(), 0);
+    }
+
+    #[test]
+    fn clone() {
+        use std::rc::Rc;
+
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(Rc::strong_count(list.head.as_ref().unwrap()), 1);
+
+        let cloned = list.clone();
+        assert_eq!(Rc::strong_count(list.head.as_ref().unwrap()), 2);
+        assert!(Rc::ptr_eq(list.head.as_ref().unwrap(), cloned.head.as_ref().unwrap()));
+        assert!(list.iter().eq(cloned.iter()));
+
+        drop(list);
+        assert_eq!(Rc::strong_count(cloned.head.as_ref().unwrap()), 1);
+        assert_eq!(cloned.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn reverse() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let reversed = list.reverse();
+        assert_eq!(reversed.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(reversed.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        assert!(List::<i32>::new().reverse().is_empty());
+    }
+
+    #[test]
+    fn append() {
+        use std::rc::Rc;
+
+        let first = List::new().prepend(2).prepend(1);
+        let second = List::new().prepend(4).prepend(3);
+        assert_eq!(Rc::strong_count(second.head.as_ref().unwrap()), 1);
+
+        let joined = first.append(&second);
+        assert_eq!(joined.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(joined.len(), 4);
+        assert_eq!(Rc::strong_count(second.head.as_ref().unwrap()), 2);
+
+        // Neither input is touched
+        assert_eq!(first.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(second.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+
+        let empty = List::new();
+        assert_eq!(empty.append(&second).iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(first.append(&empty).iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn eq_debug() {
+        let a = List::new().prepend(1).prepend(2).prepend(3);
+        let b = List::new().prepend(1).prepend(2).prepend(3);
+        assert_eq!(a, b);
+        assert_ne!(a, a.tail());
+        assert_ne!(a, List::new().prepend(0).prepend(2).prepend(3));
+        assert_eq!(List::<i32>::new(), List::new());
+
+        assert_eq!(format!("{:?}", a), "[3, 2, 1]");
+        assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn from_iter() {
+        let list: List<i32> = (0..4).collect();
+        assert_eq!(list.head(), Some(&0));
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        let empty: List<i32> = std::iter::empty().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn skip_take() {
+        use std::rc::Rc;
+
+        let list: List<i32> = (0..4).collect();
+
+        let rest = list.skip(2);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(rest.len(), 2);
+        // Shared by the node before it in `list` and by `rest`
+        assert_eq!(Rc::strong_count(rest.head.as_ref().unwrap()), 2);
+        assert_eq!(list.skip(0), list);
+        assert!(list.skip(4).is_empty());
+        assert!(list.skip(10).is_empty());
+        assert!(list.skip(usize::MAX).is_empty());
+
+        let front = list.take(2);
+        assert_eq!(front.iter().copied().collect::<Vec<_>>(), vec![0, 1]);
+        assert!(list.take(0).is_empty());
+        assert_eq!(list.take(4), list);
+        assert_eq!(list.take(10), list);
+    }
+
+    #[test]
+    fn head_ref_count() {
+        assert_eq!(List::<i32>::new().head_ref_count(), 0);
+
+        let base = List::new().prepend(1);
+        assert_eq!(base.head_ref_count(), 1);
+
+        let a = base.prepend(2);
+        let b = base.prepend(3);
+        // base itself, plus the next pointers of a and b
+        assert_eq!(base.head_ref_count(), 3);
+        assert_eq!(a.head_ref_count(), 1);
+        assert_eq!(b.head_ref_count(), 1);
+
+        drop(a);
+        assert_eq!(base.head_ref_count(), 2);
+    }
+
+    #[test]
+    fn get() {
+        let list: List<i32> = (0..4).collect();
+        assert_eq!(list.get(0), Some(&0));
+        assert_eq!(list.get(0), list.head());
+        assert_eq!(list.get(2), Some(&2));
+        assert_eq!(list.get(3), Some(&3));
+        assert_eq!(list.get(4), None);
+    }
+
+    #[test]
+    fn iter_enumerated() {
+        let list = List::new().prepend("c").prepend("b").prepend("a");
+        assert_eq!(
+            list.iter_enumerated().collect::<Vec<_>>(),
+            vec![(0, &"a"), (1, &"b"), (2, &"c")]
+        );
+        assert_eq!(list.tail().iter_enumerated().next(), Some((0, &"b")));
+        assert_eq!(List::<i32>::new().iter_enumerated().next(), None);
+    }
+
+    #[test]
+    fn map() {
+        let list: List<i32> = (1..=3).collect();
+        let strings = list.map(|elem| elem.to_string());
+        assert_eq!(strings.iter().cloned().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+        assert_eq!(strings.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        assert!(List::<i32>::new().map(|elem| elem * 2).is_empty());
+    }
+
+    #[test]
+    fn filter() {
+        use std::rc::Rc;
+
+        let list: List<i32> = (1..=4).collect();
+        let odds = list.filter(|elem| elem % 2 != 0);
+        assert_eq!(odds.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(odds.len(), 2);
+
+        assert!(list.filter(|_| false).is_empty());
+
+        let all = list.filter(|_| true);
+        assert_eq!(all, list);
+        // Fresh nodes, nothing shared with the source
+        assert!(!Rc::ptr_eq(all.head.as_ref().unwrap(), list.head.as_ref().unwrap()));
+        assert_eq!(list.head_ref_count(), 1);
+    }
+
+    #[test]
+    fn to_vec() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+        let vec = list.to_vec();
+        assert_eq!(vec, vec![3, 2, 1]);
+        assert_eq!(vec.capacity(), 3);
+        assert_eq!(vec.into_iter().collect::<List<_>>(), list);
+
+        assert_eq!(list.tail().to_vec(), vec![2, 1]);
+        assert!(List::<i32>::new().to_vec().is_empty());
+    }
+
+    #[test]
+    fn hash_eq() {
+        use std::collections::HashSet;
+
+        let shared = List::new().prepend(1);
+        let a = shared.prepend(2).prepend(3);
+        let b = List::new().prepend(1).prepend(2).prepend(3);
+        let c: List<i32> = vec![3, 2, 1].into_iter().collect();
+
+        let mut set = HashSet::new();
+        assert!(set.insert(a));
+        assert!(!set.insert(b));
+        assert!(!set.insert(c));
+        assert!(set.insert(shared.prepend(2)));
+        assert!(set.insert(List::new()));
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn into_vec() {
+        use std::rc::Rc;
+
+        // Uniquely owned: elements are moved out, not cloned
+        let unique = List::new().prepend(Rc::new(1)).prepend(Rc::new(2));
+        let moved = unique.into_vec();
+        assert_eq!(moved.iter().map(|rc| **rc).collect::<Vec<_>>(), vec![2, 1]);
+        assert!(moved.iter().all(|rc| Rc::strong_count(rc) == 1));
+
+        // The tail is still shared with `other`, so those elements get cloned
+        let other = List::new().prepend(Rc::new(1));
+        let list = other.prepend(Rc::new(2)).prepend(Rc::new(3));
+        let vec = list.into_vec();
+        assert_eq!(vec.iter().map(|rc| **rc).collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(Rc::strong_count(&vec[0]), 1);
+        assert_eq!(Rc::strong_count(&vec[2]), 2);
+        assert_eq!(other.head_ref_count(), 1);
+        assert_eq!(other.to_vec().len(), 1);
+
+        assert!(List::<i32>::new().into_vec().is_empty());
+    }
+
+    #[test]
+    fn split_first() {
+        assert!(List::<i32>::new().split_first().is_none());
+
+        let list: List<i32> = (1..=3).collect();
+        let (head, rest) = list.split_first().unwrap();
+        assert_eq!(*head, 1);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(rest, list.tail());
+
+        let single = List::new().prepend(7);
+        let (head, rest) = single.split_first().unwrap();
+        assert_eq!(*head, 7);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn from_vec() {
+        let list = List::from_vec(vec![1, 2, 3]);
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(List::from_vec(list.to_vec()), list);
+
+        // No Clone bound needed
+        struct NotClone(i32);
+        let list = List::from_vec(vec![NotClone(4), NotClone(5)]);
+        assert_eq!(list.head().map(|elem| elem.0), Some(4));
+
+        assert!(List::<i32>::from_vec(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn contains() {
+        let list: List<i32> = (1..=3).collect();
+        assert!(list.contains(&1));
+        assert!(list.contains(&3));
+        assert!(!list.contains(&4));
+        assert!(!list.tail().contains(&1));
+        assert!(!List::<i32>::new().contains(&1));
+    }
+
+    #[test]
+    fn nth_count() {
+        let list: List<i32> = (0..4).collect();
+        assert_eq!(list.nth(0), Some(&0));
+        assert_eq!(list.nth(2), Some(&2));
+        assert_eq!(list.nth(3), Some(&3));
+        assert_eq!(list.nth(4), None);
+        // Same answer on repeated calls
+        assert_eq!(list.nth(2), Some(&2));
+
+        assert_eq!(list.count(), 4);
+        assert_eq!(list.count(), list.iter().count());
+        assert_eq!(list.skip(1).count(), 3);
+        assert_eq!(List::<i32>::new().count(), 0);
+        assert_eq!(List::<i32>::new().nth(0), None);
+    }
+
+    #[test]
+    fn concat_all() {
+        use super::concat_all;
+
+        let a = List::from_vec(vec![1, 2]);
+        let b = List::from_vec(vec![3]);
+        let c = List::from_vec(vec![4, 5]);
+        assert_eq!(c.head_ref_count(), 1);
+
+        let joined = concat_all(&[a.clone(), b.clone(), c.clone()]);
+        assert_eq!(joined.to_vec(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(joined.len(), 5);
+        // c's nodes are shared by the result, a and b are copied
+        assert_eq!(c.head_ref_count(), 2);
+        assert_eq!(a.head_ref_count(), 1);
+        assert_eq!(b.head_ref_count(), 1);
+
+        assert!(concat_all::<i32>(&[]).is_empty());
+        assert_eq!(concat_all(std::slice::from_ref(&a)), a);
+    }
 }
@@ -0,0 +1,107 @@
+//用两个good_stack实现的FIFO队列：enqueue压入inbox，dequeue从outbox弹出，
+//outbox空了才把inbox整个倒过去，每个元素最多被搬一次，所以均摊O(1)
+use crate::good_stack::List;
+
+pub struct Queue<T> {
+    inbox: List<T>,
+    outbox: List<T>,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Queue { inbox: List::new(), outbox: List::new() }
+    }
+
+    pub fn enqueue(&mut self, elem: T) {
+        self.inbox.push(elem);
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.outbox.is_empty() {
+            //倒过去之后顺序正好反过来，最早进来的元素到了outbox的head
+            while let Some(elem) = self.inbox.pop() {
+                self.outbox.push(elem);
+            }
+        }
+        self.outbox.pop()
+    }
+
+    //outbox为空时最早的元素在inbox的末尾，需要O(n)走过去，但不用为了peek修改队列
+    pub fn peek_front(&self) -> Option<&T> {
+        self.outbox.peek().or_else(|| self.inbox.nth_from_tail(0))
+    }
+
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Queue;
+
+    #[test]
+    fn basics() {
+        let mut queue = Queue::new();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.peek_front(), None);
+
+        queue.enqueue(1);
+        queue.enqueue(2);
+        queue.enqueue(3);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek_front(), Some(&1));
+        assert_eq!(queue.dequeue(), Some(1));
+
+        // 2 and 3 now sit in the outbox while 4 and 5 land in the inbox
+        queue.enqueue(4);
+        queue.enqueue(5);
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.peek_front(), Some(&2));
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(3));
+
+        // The second flip keeps FIFO order too
+        assert_eq!(queue.peek_front(), Some(&4));
+        assert_eq!(queue.dequeue(), Some(4));
+        queue.enqueue(6);
+        assert_eq!(queue.dequeue(), Some(5));
+        assert_eq!(queue.dequeue(), Some(6));
+        assert_eq!(queue.dequeue(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn interleaved() {
+        let mut queue = Queue::new();
+        let mut next_in = 0;
+        let mut next_out = 0;
+        for round in 0..100 {
+            for _ in 0..(round % 7) {
+                queue.enqueue(next_in);
+                next_in += 1;
+            }
+            for _ in 0..(round % 5) {
+                match queue.dequeue() {
+                    Some(elem) => {
+                        assert_eq!(elem, next_out);
+                        next_out += 1;
+                    }
+                    None => assert_eq!(next_in, next_out),
+                }
+            }
+            assert_eq!(queue.len(), next_in - next_out);
+        }
+    }
+}